@@ -1,33 +1,23 @@
 use soroban_sdk::{symbol_short, Symbol};
 
-// Storage Keys
-pub const STORAGE_USERS: Symbol = symbol_short!("users");
-pub const STORAGE_POLICIES: Symbol = symbol_short!("policies");
-pub const STORAGE_SUBS: Symbol = symbol_short!("subs");
-pub const STORAGE_CLAIMS: Symbol = symbol_short!("claims");
-pub const STORAGE_DAOPROP: Symbol = symbol_short!("daoprop");
-pub const STORAGE_DAOVOTE: Symbol = symbol_short!("daovote");
-pub const STORAGE_INVPOOL: Symbol = symbol_short!("invpool");
-pub const STORAGE_CREDIT: Symbol = symbol_short!("credit");
-
-// Legacy storage keys for compatibility
-pub const USERS: Symbol = symbol_short!("users");
-pub const POLICIES: Symbol = symbol_short!("policies");
-pub const SUBSCRIPTIONS: Symbol = symbol_short!("subs");
-pub const CLAIMS: Symbol = symbol_short!("claims");
-pub const PROPOSALS: Symbol = symbol_short!("daoprop");
-pub const DAO_VOTES: Symbol = symbol_short!("daovote");
-pub const VOTE_DELEGATIONS: Symbol = symbol_short!("votedel");
+// Instance storage keys
 pub const PLATFORM_CONFIG: Symbol = symbol_short!("platform");
 pub const SAFETY_POOL: Symbol = symbol_short!("safety");
+pub const PREMIUM_TOKEN: Symbol = symbol_short!("premtok");
+pub const TOTAL_STAKED: Symbol = symbol_short!("staked");
+pub const ADMIN: Symbol = symbol_short!("admin");
+pub const COUNCIL_MEMBERS: Symbol = symbol_short!("council");
+
+// Legacy instance maps, drained by `migrate_legacy_records`
 pub const PREMIUM_PAYMENTS: Symbol = symbol_short!("payments");
-pub const CLAIM_ASSESSMENTS: Symbol = symbol_short!("assess");
-pub const CLAIM_VOTES: Symbol = symbol_short!("clmvote");
+pub const CLAIMS: Symbol = symbol_short!("claims");
+
+// Admin Events
+pub const CONTRACT_INITIALIZED: Symbol = symbol_short!("Init");
+pub const ADMIN_TRANSFERRED: Symbol = symbol_short!("AdmXfer");
+pub const ADMIN_RENOUNCED: Symbol = symbol_short!("AdmRen");
 
 // User Events
-pub const EVENT_USER_REG: Symbol = symbol_short!("UsrReg");
-pub const EVENT_USER_BAN: Symbol = symbol_short!("UsrBan");
-pub const EVENT_DAO_ADD: Symbol = symbol_short!("DAOAdd");
 pub const EVENT_DAO_REM: Symbol = symbol_short!("DAORem");
 
 // Policy Events
@@ -37,84 +27,117 @@ pub const EVENT_POL_DEL: Symbol = symbol_short!("PolDel");
 
 // Subscription Events
 pub const EVENT_SUB_CRT: Symbol = symbol_short!("SubCrt");
-pub const EVENT_SUB_PAY: Symbol = symbol_short!("SubPay");
-pub const EVENT_SUB_GRC: Symbol = symbol_short!("SubGrc");
 pub const EVENT_SUB_SUS: Symbol = symbol_short!("SubSus");
 
-// Claim Events
-pub const EVENT_CLM_SUB: Symbol = symbol_short!("ClmSub");
-pub const EVENT_CLM_VOT: Symbol = symbol_short!("ClmVot");
-pub const EVENT_CLM_APP: Symbol = symbol_short!("ClmApp");
-pub const EVENT_CLM_REJ: Symbol = symbol_short!("ClmRej");
-pub const EVENT_CLM_PAY: Symbol = symbol_short!("ClmPay");
-
-// Investment Events
-pub const EVENT_INV_DEP: Symbol = symbol_short!("InvDep");
-pub const EVENT_INV_WDR: Symbol = symbol_short!("InvWdr");
-pub const EVENT_INV_YLD: Symbol = symbol_short!("InvYld");
-
 // DAO Events
 pub const PROPOSAL_CREATED: Symbol = symbol_short!("PropCrt");
 pub const VOTE_CAST: Symbol = symbol_short!("VoteCast");
 pub const PROPOSAL_EXECUTED: Symbol = symbol_short!("PropExe");
+pub const PROPOSAL_FINALIZED: Symbol = symbol_short!("PropFin");
 pub const VOTE_DELEGATED: Symbol = symbol_short!("VoteDel");
+pub const DELEGATION_REVOKED: Symbol = symbol_short!("DelRev");
+pub const DELEGATION_OVERRIDDEN: Symbol = symbol_short!("DelOvr");
+pub const PROPOSAL_CONFIG_UPDATED: Symbol = symbol_short!("PropCfg");
+pub const PROPOSAL_CANCELLED: Symbol = symbol_short!("PropCan");
+pub const VETO_CAST: Symbol = symbol_short!("VetoCast");
+pub const PROPOSAL_VETOED: Symbol = symbol_short!("PropVeto");
+pub const PROPOSAL_INVALIDATED: Symbol = symbol_short!("PropInv");
+pub const PROPOSAL_QUEUED: Symbol = symbol_short!("PropQue");
+pub const VOTE_COMMITTED: Symbol = symbol_short!("VoteCmt");
+pub const VOTE_REVEALED: Symbol = symbol_short!("VoteRev");
 
 // Claim Events
 pub const CLAIM_SUBMITTED: Symbol = symbol_short!("ClmSub");
 pub const CLAIM_ASSESSED: Symbol = symbol_short!("ClmAss");
-pub const CLAIM_VOTED: Symbol = symbol_short!("ClmVot");
+pub const ASSESSORS_ASSIGNED: Symbol = symbol_short!("ClmAsgn");
 pub const CLAIM_APPROVED: Symbol = symbol_short!("ClmApp");
 pub const CLAIM_REJECTED: Symbol = symbol_short!("ClmRej");
-pub const CLAIM_DISPUTED: Symbol = symbol_short!("ClmDis");
 pub const CLAIM_PAID: Symbol = symbol_short!("ClmPay");
 
 // Financial Events
 pub const EXTERNAL_FUNDING_ADDED: Symbol = symbol_short!("ExtFund");
 pub const RESERVE_FUNDS_WITHDRAWN: Symbol = symbol_short!("ResWdr");
-pub const INVESTMENT_RETURNS_UPDATED: Symbol = symbol_short!("InvRet");
 pub const MINIMUM_RESERVE_UPDATED: Symbol = symbol_short!("MinRes");
 pub const RESERVE_RATIO_UPDATED: Symbol = symbol_short!("ResRat");
 pub const FINANCIAL_AUDIT_COMPLETED: Symbol = symbol_short!("FinAud");
 pub const AUDIT_DISCREPANCY_FOUND: Symbol = symbol_short!("AudDis");
 pub const PLATFORM_CONFIG_UPDATED: Symbol = symbol_short!("PlatCfg");
+pub const PREMIUM_TOKEN_UPDATED: Symbol = symbol_short!("PremTok");
 pub const EMERGENCY_FUND_FREEZE: Symbol = symbol_short!("EmerFrz");
 pub const EMERGENCY_FUND_UNFREEZE: Symbol = symbol_short!("EmerUnfrz");
 
 // Subscription Events
-pub const PLAN_SUBSCRIBED: Symbol = symbol_short!("PlanSub");
 pub const PREMIUM_PAID: Symbol = symbol_short!("PremPaid");
-pub const SUBSCRIPTION_CANCELLED: Symbol = symbol_short!("SubCan");
-pub const SUBSCRIPTION_REACTIVATED: Symbol = symbol_short!("SubReact");
-pub const SUBSCRIPTION_GRACE_PERIOD: Symbol = symbol_short!("SubGrace");
-pub const SUBSCRIPTION_SUSPENDED: Symbol = symbol_short!("SubSus");
-
-// Policy Events
-pub const POL_PROP: Symbol = symbol_short!("PolProp");
-pub const POL_ARCH: Symbol = symbol_short!("PolArch");
-pub const POL_DEL: Symbol = symbol_short!("PolDel");
-pub const POL_ACT: Symbol = symbol_short!("PolAct");
 
 // User Events
 pub const USR_REG: Symbol = symbol_short!("UsrReg");
 pub const USR_BAN: Symbol = symbol_short!("UsrBan");
 pub const USR_DAO: Symbol = symbol_short!("UsrDao");
+pub const USR_HOUSEHOLD: Symbol = symbol_short!("UsrHh");
 pub const REP_UPD: Symbol = symbol_short!("RepUpd");
 
-// Platform Configuration Constants
-pub const CONFIG_GRACE_WEEKS: u64 = 2;
-pub const CONFIG_MIN_QUORUM: u32 = 3;
-pub const CONFIG_PROP_DAYS: u64 = 7;
-pub const CONFIG_CLAIM_COOLDOWN: u32 = 30;
-pub const CONFIG_INV_LOCKIN: u32 = 90;
-pub const CONFIG_INTEREST_RATE: u32 = 500;  // 5% in basis points
-pub const CONFIG_CREDIT_SLASH: u32 = 10;    // 10 points slashed on claim rejection
-
-// Default values for platform configuration
-pub const DEFAULT_GRACE_PERIOD_WEEKS: u64 = 2;
-pub const DEFAULT_MINIMUM_QUORUM: u32 = 3;
-pub const DEFAULT_PROPOSAL_DURATION_DAYS: u64 = 7;
-pub const DEFAULT_MAX_CLAIM_AMOUNT_RATIO: u64 = 80;
-pub const DEFAULT_PENALTY_RATE: u32 = 500;
-pub const DEFAULT_COUNCIL_SIZE: u64 = 5;
-pub const DEFAULT_RESERVE_RATIO: u64 = 7000; // 70% in basis points
-pub const DEFAULT_MINIMUM_RESERVE: i128 = 10000;
+// Staking Events
+pub const STAKE_ADDED: Symbol = symbol_short!("StkAdd");
+pub const UNSTAKE_REQUESTED: Symbol = symbol_short!("StkReq");
+pub const STAKE_WITHDRAWN: Symbol = symbol_short!("StkWdr");
+pub const STAKE_SLASHED: Symbol = symbol_short!("StkSlash");
+
+// Proposal rules; durations in seconds, thresholds in basis points,
+// quorum multipliers in percent of the platform's minimum quorum
+pub const SECONDS_PER_DAY: u64 = 86_400;
+pub const BASE_QUORUM_MULTIPLIER: u32 = 100;
+
+// Queued proposals may execute for this long after their ETA
+pub const TIMELOCK_EXECUTION_WINDOW: u64 = 7 * SECONDS_PER_DAY;
+// Treasury and governance types may skip the timelock only with at least this threshold
+pub const TIMELOCK_BYPASS_THRESHOLD: u32 = 7500;
+
+// Removals and bans never shrink the council below this many members
+pub const MIN_COUNCIL_SIZE: u32 = 3;
+
+// Most actions a single batched proposal may carry
+pub const MAX_BATCH_ACTIONS: u32 = 10;
+
+// Conviction tallies add this much (basis points) per full day a vote is held, up to the cap
+pub const CONVICTION_DAILY_GROWTH: u32 = 1000;
+pub const CONVICTION_MAX_GROWTH: u32 = 10000;
+
+// Claim votes scale quorum with the amount claimed: up to 100 XLM needs the base quorum,
+// up to 1000 XLM one and a half times it, anything larger twice it (amounts in stroops)
+pub const CLAIM_SMALL_AMOUNT: i128 = 1_000_000_000;
+pub const CLAIM_MEDIUM_AMOUNT: i128 = 10_000_000_000;
+pub const CLAIM_MEDIUM_QUORUM_MULTIPLIER: u32 = 150;
+pub const CLAIM_LARGE_QUORUM_MULTIPLIER: u32 = 200;
+
+// Most assessors a single claim may have
+pub const MAX_CLAIM_ASSESSORS: u32 = 5;
+
+// Largest page returned by the proposal listings
+pub const MAX_PAGE_SIZE: u32 = 20;
+
+// Unstaked tokens stay locked (and slashable) for this long before they can be withdrawn.
+// Longer than the longest default `UserBan` window (14 days), leaving a week to execute a slash
+// voted on after the request.
+pub const STAKE_UNBONDING_PERIOD: u64 = 21 * SECONDS_PER_DAY;
+
+// Delegation chains longer than this are not followed when counting a delegate's vote
+pub const MAX_DELEGATION_DEPTH: u32 = 5;
+
+// Share of the active council (basis points) whose vetoes block a proposal; two thirds
+pub const VETO_THRESHOLD: u32 = 6666;
+
+// Vote weight = base + reputation boost + stake boost, so no member exceeds 11
+pub const VOTE_BASE_WEIGHT: u32 = 1;
+pub const VOTE_REPUTATION_STEP: u32 = 100;             // +1 per 100 reputation points
+pub const VOTE_MAX_REPUTATION_BOOST: u32 = 5;          // reputation is capped at 500
+pub const VOTE_STAKE_STEP: i128 = 1_000_000_000;       // +1 per 100 XLM staked (stroops)
+pub const VOTE_MAX_STAKE_BOOST: u32 = 5;
+
+// Storage TTL policy (in ledgers, ~5s each)
+pub const DAY_IN_LEDGERS: u32 = 17280;
+pub const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+pub const LIVE_RECORD_BUMP_AMOUNT: u32 = 120 * DAY_IN_LEDGERS;    // users, active policies/subscriptions, open claims/proposals
+pub const LIVE_RECORD_LIFETIME_THRESHOLD: u32 = LIVE_RECORD_BUMP_AMOUNT - DAY_IN_LEDGERS;
+pub const ARCHIVED_RECORD_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS; // archived, cancelled, settled records
+pub const ARCHIVED_RECORD_LIFETIME_THRESHOLD: u32 = ARCHIVED_RECORD_BUMP_AMOUNT - DAY_IN_LEDGERS;
@@ -0,0 +1,221 @@
+use soroban_sdk::contracterror;
+use crate::instructions::user_management::UserManagementError;
+use crate::instructions::dao_governance::DAOGovernanceError;
+use crate::instructions::policy_management::PolicyManagementError;
+use crate::instructions::subscription_management::SubscriptionManagementError;
+use crate::instructions::claims_processing::ClaimsProcessingError;
+use crate::instructions::financial_management::FinancialManagementError;
+use crate::instructions::admin::AdminError;
+use crate::instructions::staking::StakingError;
+
+// Contract-level error returned by every entrypoint.
+//
+// Codes are stable and grouped by module so clients can map them to messages.
+// Never renumber an existing variant; add new ones at the end of their range.
+// The contract spec caps an error enum at 50 variants and all 50 are in use, so a new
+// code has to replace one that is no longer returned.
+//   1-99    shared
+//   100-199 user management
+//   200-299 DAO governance
+//   300-399 policy management
+//   400-499 subscription management
+//   500-599 claims processing
+//   600-699 financial management
+//   700-799 administration
+//   800-899 staking
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // Shared
+    Unauthorized = 1,
+
+    // User management
+    UserAlreadyExists = 100,
+    UserNotFound = 101,
+    CouncilFull = 102,
+    CouncilTooSmall = 103,
+    NotCouncilMember = 104,
+
+    // DAO governance
+    ProposalNotFound = 200,
+    ProposalAlreadyExecuted = 201,
+    ProposalNotOpen = 202,
+    VotingPeriodEnded = 203,
+    AlreadyVoted = 204,
+    InvalidVoteType = 206,
+    InvalidProposalPayload = 207,
+    VoteNotFound = 208,
+    InvalidVotingPeriod = 209,
+    InvalidProposalConfig = 210,
+    InsufficientReputation = 211,
+    VotingStillOpen = 212,
+    DelegationNotFound = 213,
+    InvalidDelegation = 214,
+    DelegationCycle = 215,
+    VotesAlreadyCast = 216,
+    AlreadyVetoed = 217,
+    TimelockActive = 218,
+    RevealMismatch = 219,
+
+    // Policy management
+    PolicyNotFound = 300,
+    InvalidPolicyData = 302,
+    InsufficientCoverage = 303,
+    PolicyNotActive = 304,
+
+    // Subscription management
+    SubscriptionNotFound = 400,
+    InvalidSubscriptionData = 401,
+    InsufficientFunds = 402,
+    SubscriptionNotActive = 403,
+    PaymentFailed = 404,
+
+    // Claims processing
+    ClaimNotFound = 500,
+    InvalidClaimData = 501,
+    ClaimAlreadyProcessed = 502,
+    ClaimAlreadyPaid = 505,
+    ConflictOfInterest = 507,
+    AlreadyAssessed = 508,
+
+    // Financial management
+    InvalidAmount = 600,
+    InsufficientReserves = 601,
+    InvalidRatio = 602,
+    FundFrozen = 603,
+    TokenInUse = 606,
+
+    // Administration
+    InvalidCouncil = 702,
+    InvalidConfig = 703,
+
+    // Staking
+    InsufficientStake = 800,
+    NothingToWithdraw = 801,
+    StakeStillUnbonding = 802,
+}
+
+impl From<UserManagementError> for Error {
+    fn from(err: UserManagementError) -> Self {
+        match err {
+            UserManagementError::UserAlreadyExists => Error::UserAlreadyExists,
+            UserManagementError::UserNotFound => Error::UserNotFound,
+            UserManagementError::Unauthorized => Error::Unauthorized,
+            UserManagementError::CouncilFull => Error::CouncilFull,
+            UserManagementError::CouncilTooSmall => Error::CouncilTooSmall,
+            UserManagementError::NotCouncilMember => Error::NotCouncilMember,
+        }
+    }
+}
+
+impl From<DAOGovernanceError> for Error {
+    fn from(err: DAOGovernanceError) -> Self {
+        match err {
+            DAOGovernanceError::ProposalNotFound => Error::ProposalNotFound,
+            DAOGovernanceError::ProposalNotOpen => Error::ProposalNotOpen,
+            DAOGovernanceError::VotingPeriodEnded => Error::VotingPeriodEnded,
+            DAOGovernanceError::AlreadyVoted => Error::AlreadyVoted,
+            DAOGovernanceError::Unauthorized => Error::Unauthorized,
+            DAOGovernanceError::InvalidVoteType => Error::InvalidVoteType,
+            DAOGovernanceError::InvalidProposalPayload => Error::InvalidProposalPayload,
+            DAOGovernanceError::VoteNotFound => Error::VoteNotFound,
+            DAOGovernanceError::InvalidVotingPeriod => Error::InvalidVotingPeriod,
+            DAOGovernanceError::InvalidProposalConfig => Error::InvalidProposalConfig,
+            DAOGovernanceError::InsufficientReputation => Error::InsufficientReputation,
+            DAOGovernanceError::VotingStillOpen => Error::VotingStillOpen,
+            DAOGovernanceError::DelegationNotFound => Error::DelegationNotFound,
+            DAOGovernanceError::InvalidDelegation => Error::InvalidDelegation,
+            DAOGovernanceError::DelegationCycle => Error::DelegationCycle,
+            DAOGovernanceError::VotesAlreadyCast => Error::VotesAlreadyCast,
+            DAOGovernanceError::AlreadyVetoed => Error::AlreadyVetoed,
+            DAOGovernanceError::TimelockActive => Error::TimelockActive,
+            DAOGovernanceError::RevealMismatch => Error::RevealMismatch,
+        }
+    }
+}
+
+impl From<PolicyManagementError> for Error {
+    fn from(err: PolicyManagementError) -> Self {
+        match err {
+            PolicyManagementError::PolicyNotFound => Error::PolicyNotFound,
+            PolicyManagementError::Unauthorized => Error::Unauthorized,
+            PolicyManagementError::InvalidPolicyData => Error::InvalidPolicyData,
+            PolicyManagementError::PolicyNotActive => Error::PolicyNotActive,
+        }
+    }
+}
+
+impl From<SubscriptionManagementError> for Error {
+    fn from(err: SubscriptionManagementError) -> Self {
+        match err {
+            SubscriptionManagementError::SubscriptionNotFound => Error::SubscriptionNotFound,
+            SubscriptionManagementError::PolicyNotFound => Error::PolicyNotFound,
+            SubscriptionManagementError::Unauthorized => Error::Unauthorized,
+            SubscriptionManagementError::InvalidSubscriptionData => Error::InvalidSubscriptionData,
+            SubscriptionManagementError::InsufficientFunds => Error::InsufficientFunds,
+            SubscriptionManagementError::SubscriptionNotActive => Error::SubscriptionNotActive,
+            SubscriptionManagementError::PaymentFailed => Error::PaymentFailed,
+            SubscriptionManagementError::PolicyNotActive => Error::PolicyNotActive,
+        }
+    }
+}
+
+impl From<ClaimsProcessingError> for Error {
+    fn from(err: ClaimsProcessingError) -> Self {
+        match err {
+            ClaimsProcessingError::ClaimNotFound => Error::ClaimNotFound,
+            ClaimsProcessingError::PolicyNotFound => Error::PolicyNotFound,
+            ClaimsProcessingError::Unauthorized => Error::Unauthorized,
+            ClaimsProcessingError::InvalidClaimData => Error::InvalidClaimData,
+            ClaimsProcessingError::PolicyNotActive => Error::PolicyNotActive,
+            ClaimsProcessingError::InsufficientCoverage => Error::InsufficientCoverage,
+            ClaimsProcessingError::ClaimAlreadyProcessed => Error::ClaimAlreadyProcessed,
+            ClaimsProcessingError::ClaimAlreadyPaid => Error::ClaimAlreadyPaid,
+            ClaimsProcessingError::PaymentFailed => Error::PaymentFailed,
+            ClaimsProcessingError::InsufficientReserves => Error::InsufficientReserves,
+            ClaimsProcessingError::FundFrozen => Error::FundFrozen,
+            ClaimsProcessingError::ConflictOfInterest => Error::ConflictOfInterest,
+            ClaimsProcessingError::AlreadyAssessed => Error::AlreadyAssessed,
+        }
+    }
+}
+
+impl From<FinancialManagementError> for Error {
+    fn from(err: FinancialManagementError) -> Self {
+        match err {
+            FinancialManagementError::Unauthorized => Error::Unauthorized,
+            FinancialManagementError::InvalidAmount => Error::InvalidAmount,
+            FinancialManagementError::InsufficientReserves => Error::InsufficientReserves,
+            FinancialManagementError::InvalidRatio => Error::InvalidRatio,
+            FinancialManagementError::FundFrozen => Error::FundFrozen,
+            FinancialManagementError::TokenInUse => Error::TokenInUse,
+            FinancialManagementError::InvalidConfig => Error::InvalidConfig,
+        }
+    }
+}
+
+impl From<AdminError> for Error {
+    fn from(err: AdminError) -> Self {
+        match err {
+            AdminError::Unauthorized => Error::Unauthorized,
+            AdminError::InvalidCouncil => Error::InvalidCouncil,
+            AdminError::InvalidConfig => Error::InvalidConfig,
+        }
+    }
+}
+
+impl From<StakingError> for Error {
+    fn from(err: StakingError) -> Self {
+        match err {
+            StakingError::UserNotFound => Error::UserNotFound,
+            StakingError::Unauthorized => Error::Unauthorized,
+            StakingError::InvalidAmount => Error::InvalidAmount,
+            StakingError::InsufficientStake => Error::InsufficientStake,
+            StakingError::NothingToWithdraw => Error::NothingToWithdraw,
+            StakingError::StakeStillUnbonding => Error::StakeStillUnbonding,
+            StakingError::PaymentFailed => Error::PaymentFailed,
+            StakingError::InsufficientFunds => Error::InsufficientFunds,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use soroban_sdk::{Address, Env, Vec};
+use crate::constant::{ADMIN, CONTRACT_INITIALIZED, ADMIN_TRANSFERRED, ADMIN_RENOUNCED};
+use crate::state::PlatformConfig;
+use crate::instructions::user_management::enroll_council_member;
+use crate::instructions::financial_management::FinancialManagementService;
+use crate::instructions::dao_governance::seed_proposal_configs;
+
+#[derive(Debug)]
+pub enum AdminError {
+    Unauthorized,
+    InvalidCouncil,
+    InvalidConfig,
+}
+
+// Deploy-time setup, run once by the contract constructor so nobody can get in
+// between deploying and configuring: records the admin and premium token, seeds the
+// platform config, per-type proposal rules and reserve minimums, and enrolls the
+// first council so governance has members able to vote.
+pub fn initialize(
+    env: &Env,
+    admin: Address,
+    premium_token: Address,
+    council: Vec<Address>,
+    config: PlatformConfig,
+    minimum_reserve: i128,
+    reserve_ratio: u64,
+) -> Result<(), AdminError> {
+    admin.require_auth();
+
+    if !FinancialManagementService::is_valid_config(&config) {
+        return Err(AdminError::InvalidConfig);
+    }
+    if council.is_empty() || council.len() as u64 > config.council_size {
+        return Err(AdminError::InvalidCouncil);
+    }
+
+    FinancialManagementService::bootstrap(env, premium_token.clone(), config, minimum_reserve, reserve_ratio)
+        .map_err(|_| AdminError::InvalidConfig)?;
+
+    seed_proposal_configs(env);
+    for member in council.iter() {
+        enroll_council_member(env, &member);
+    }
+
+    env.storage().instance().set(&ADMIN, &admin);
+    env.events().publish((CONTRACT_INITIALIZED, admin), (premium_token, council.len()));
+    Ok(())
+}
+
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&ADMIN)
+}
+
+pub fn is_admin(env: &Env, address: &Address) -> bool {
+    get_admin(env).is_some_and(|admin| admin == *address)
+}
+
+// Hands the admin role to a new address. Both parties must sign so the role
+// can't be sent to an address nobody controls.
+pub fn transfer_admin(env: &Env, new_admin: Address) -> Result<bool, AdminError> {
+    let admin = require_admin(env)?;
+    new_admin.require_auth();
+
+    env.storage().instance().set(&ADMIN, &new_admin);
+    env.events().publish((ADMIN_TRANSFERRED, admin), new_admin);
+    Ok(true)
+}
+
+// Permanently drops the admin role, leaving the council as the only authority.
+pub fn renounce_admin(env: &Env) -> Result<bool, AdminError> {
+    let admin = require_admin(env)?;
+
+    env.storage().instance().remove(&ADMIN);
+    env.events().publish((ADMIN_RENOUNCED, admin), ());
+    Ok(true)
+}
+
+fn require_admin(env: &Env) -> Result<Address, AdminError> {
+    // After a renounce there is no admin left to act
+    let admin = get_admin(env).ok_or(AdminError::Unauthorized)?;
+    admin.require_auth();
+    Ok(admin)
+}
@@ -0,0 +1,170 @@
+use soroban_sdk::{Address, Env, String, Vec as SdkVec};
+use crate::constant::{ASSESSORS_ASSIGNED, CLAIM_ASSESSED, MAX_CLAIM_ASSESSORS};
+use crate::state::{
+    Assessment, AssessmentSummary, Claim, ClaimStatus, DataKey, ProposalStatus, ProposalType, User, UserStatus,
+};
+use crate::storage;
+use crate::instructions::claims_processing::{ClaimsProcessingError, get_claim, is_claim_pending};
+use crate::instructions::dao_governance::{get_proposal, get_proposal_config};
+use crate::instructions::user_management::{calculate_vote_weight, is_council_member};
+
+// Assigns members to assess a pending claim, moving it to `UnderReview`. Addresses already
+// assigned are skipped; more can be added while the claim's vote is still running.
+pub fn assign_assessors(
+    env: &Env,
+    claim_id: u64,
+    council_member: Address,
+    assessors: SdkVec<Address>,
+) -> Result<bool, ClaimsProcessingError> {
+    council_member.require_auth();
+    if !is_council_member(env, &council_member) {
+        return Err(ClaimsProcessingError::Unauthorized);
+    }
+
+    let mut claim = get_claim(env, claim_id)?;
+    if !is_claim_pending(&claim) || !vote_running(env, &claim) {
+        return Err(ClaimsProcessingError::ClaimAlreadyProcessed);
+    }
+    if assessors.is_empty() {
+        return Err(ClaimsProcessingError::InvalidClaimData);
+    }
+
+    let key = DataKey::ClaimAssessors(claim_id);
+    let mut assigned = storage::load_index::<Address>(env, &key);
+    for assessor in assessors.iter() {
+        if assigned.contains(&assessor) {
+            continue;
+        }
+        load_assessor(env, &claim, &assessor)?;
+        assigned.push_back(assessor);
+    }
+    if assigned.len() > MAX_CLAIM_ASSESSORS {
+        return Err(ClaimsProcessingError::InvalidClaimData);
+    }
+    storage::save_index(env, &key, &assigned);
+
+    claim.status = ClaimStatus::UnderReview;
+    storage::save(env, &claim);
+    attach_summary(env, &claim);
+
+    env.events().publish((ASSESSORS_ASSIGNED, claim_id), (council_member, assessors));
+    Ok(true)
+}
+
+// Records an assigned assessor's finding from their field visit. Each assessor assesses once,
+// weighted by their vote weight, and the claim's DAO vote carries the updated summary.
+// Findings are only taken while that vote is running.
+pub fn submit_assessment(
+    env: &Env,
+    claim_id: u64,
+    assessor: Address,
+    decision: bool,
+    reasoning: String,
+) -> Result<bool, ClaimsProcessingError> {
+    assessor.require_auth();
+    let claim = get_claim(env, claim_id)?;
+    if claim.status != ClaimStatus::UnderReview || !vote_running(env, &claim) {
+        return Err(ClaimsProcessingError::ClaimAlreadyProcessed);
+    }
+    if !get_claim_assessors(env, claim_id).contains(&assessor) {
+        return Err(ClaimsProcessingError::Unauthorized);
+    }
+    if get_claim_assessments(env, claim_id).iter().any(|assessment| assessment.assessor == assessor) {
+        return Err(ClaimsProcessingError::AlreadyAssessed);
+    }
+    // Households can change after assignment, so check again
+    let user = load_assessor(env, &claim, &assessor)?;
+
+    storage::push_index(env, &DataKey::ClaimAssessments(claim_id), Assessment {
+        assessor: assessor.clone(),
+        claim_id,
+        decision,
+        reasoning,
+        assessment_date: env.ledger().timestamp(),
+        weight: calculate_vote_weight(env, &user).total() as i128,
+    });
+    attach_summary(env, &claim);
+
+    env.events().publish((CLAIM_ASSESSED, claim_id), (assessor, decision));
+    Ok(true)
+}
+
+pub fn get_claim_assessors(env: &Env, claim_id: u64) -> SdkVec<Address> {
+    storage::load_index(env, &DataKey::ClaimAssessors(claim_id))
+}
+
+pub fn get_claim_assessments(env: &Env, claim_id: u64) -> SdkVec<Assessment> {
+    storage::load_index(env, &DataKey::ClaimAssessments(claim_id))
+}
+
+pub fn get_assessment_summary(env: &Env, claim_id: u64) -> AssessmentSummary {
+    let mut summary = AssessmentSummary {
+        assessors: get_claim_assessors(env, claim_id).len(),
+        approvals: 0,
+        rejections: 0,
+        approve_weight: 0,
+        reject_weight: 0,
+    };
+    for assessment in get_claim_assessments(env, claim_id).iter() {
+        if assessment.decision {
+            summary.approvals += 1;
+            summary.approve_weight += assessment.weight;
+        } else {
+            summary.rejections += 1;
+            summary.reject_weight += assessment.weight;
+        }
+    }
+    summary
+}
+
+// An assessor must be a registered, unbanned user with no stake in the claim: neither the
+// claimer nor anyone from the claimer's household
+fn load_assessor(env: &Env, claim: &Claim, assessor: &Address) -> Result<User, ClaimsProcessingError> {
+    let user = storage::load::<User>(env, &DataKey::User(assessor.clone()))
+        .ok_or(ClaimsProcessingError::Unauthorized)?;
+    if user.status == UserStatus::Banned {
+        return Err(ClaimsProcessingError::Unauthorized);
+    }
+    if *assessor == claim.claimer {
+        return Err(ClaimsProcessingError::ConflictOfInterest);
+    }
+    if user.household.is_some() {
+        let claimer_household = storage::load::<User>(env, &DataKey::User(claim.claimer.clone()))
+            .and_then(|claimer| claimer.household);
+        if claimer_household == user.household {
+            return Err(ClaimsProcessingError::ConflictOfInterest);
+        }
+    }
+    Ok(user)
+}
+
+// Whether the claim's DAO vote, if it has one, is open and its window has not closed
+fn vote_running(env: &Env, claim: &Claim) -> bool {
+    let Some(proposal_id) = claim.proposal_id else {
+        return true;
+    };
+    get_proposal(env, proposal_id).is_ok_and(|proposal| {
+        proposal.status == ProposalStatus::Open && env.ledger().timestamp() <= proposal.voting_period_end
+    })
+}
+
+// Puts the latest summary on the claim's DAO vote, if it has one. The vote cannot settle
+// early until every assessor has reported; once the last one does, it stays open for at least
+// the claim type's minimum duration so members can vote with the findings in hand.
+fn attach_summary(env: &Env, claim: &Claim) {
+    let Some(proposal_id) = claim.proposal_id else {
+        return;
+    };
+    if let Ok(mut proposal) = get_proposal(env, proposal_id) {
+        proposal.assessment = get_assessment_summary(env, claim.id);
+        let summary = &proposal.assessment;
+        if proposal.status == ProposalStatus::Open && summary.approvals + summary.rejections == summary.assessors {
+            let min_duration = get_proposal_config(env, ProposalType::ClaimResolution).min_duration;
+            let end = proposal.voting_period_end.max(env.ledger().timestamp() + min_duration);
+            // A secret ballot keeps its reveal window after the new end
+            proposal.reveal_end = proposal.reveal_end.map(|reveal_end| reveal_end - proposal.voting_period_end + end);
+            proposal.voting_period_end = end;
+        }
+        storage::save(env, &proposal);
+    }
+}
@@ -1,9 +1,11 @@
-use soroban_sdk::xdr::ToXdr;
-use soroban_sdk::{Env, Address, String, Vec as SdkVec, symbol_short, BytesN};
+use soroban_sdk::{token, Env, Address, String, BytesN};
 use crate::constant::{CLAIM_SUBMITTED, CLAIM_APPROVED, CLAIM_REJECTED, CLAIM_PAID};
-use crate::state::{DataKey, Claim, ClaimStatus, Policy, User, ClaimType};
+use crate::state::{DataKey, Claim, ClaimStatus, Policy, ClaimType, ProposalStatus};
 use crate::instructions::user_management::is_user_approved;
+use crate::storage::{self, next_id};
 use crate::instructions::policy_management::is_policy_active;
+use crate::instructions::financial_management::FinancialManagementService;
+use crate::instructions::dao_governance::{open_claim_vote, restart_claim_vote};
 
 #[derive(Debug)]
 pub enum ClaimsProcessingError {
@@ -14,8 +16,12 @@ pub enum ClaimsProcessingError {
     PolicyNotActive,
     InsufficientCoverage,
     ClaimAlreadyProcessed,
-    InvalidEvidence,
-    ClaimAmountExceeded,
+    ClaimAlreadyPaid,
+    PaymentFailed,
+    InsufficientReserves,
+    FundFrozen,
+    ConflictOfInterest,
+    AlreadyAssessed,
 }
 
 pub fn create_claim(
@@ -26,6 +32,7 @@ pub fn create_claim(
     image_hash: BytesN<32>,
     description: String,
 ) -> Result<u64, ClaimsProcessingError> {
+    claimer.require_auth();
     // Check if claimer is approved
     if !is_user_approved(env, &claimer) {
         return Err(ClaimsProcessingError::Unauthorized);
@@ -33,9 +40,14 @@ pub fn create_claim(
 
     // Get the subscription to validate it exists and is active
     let subscription_key = DataKey::Subscription(subscription_id);
-    let subscription = env.storage().instance().get::<_, crate::state::Subscription>(&subscription_key)
+    let subscription = storage::load::<crate::state::Subscription>(env, &subscription_key)
         .ok_or(ClaimsProcessingError::PolicyNotFound)?;
 
+    // Only the subscriber may claim against their cover
+    if subscription.subscriber != claimer {
+        return Err(ClaimsProcessingError::Unauthorized);
+    }
+
     // Check if subscription is active
     if subscription.status != crate::state::SubscriptionStatus::Active {
         return Err(ClaimsProcessingError::PolicyNotActive);
@@ -46,7 +58,7 @@ pub fn create_claim(
         return Err(ClaimsProcessingError::InvalidClaimData);
     }
 
-    let claim_id = env.ledger().sequence() as u64;
+    let claim_id = next_id(env, DataKey::LatestClaimId);
     let claim = Claim {
         id: claim_id,
         subscription_id,
@@ -55,14 +67,18 @@ pub fn create_claim(
         image_hash,
         created_at: env.ledger().timestamp(),
         plan_id: subscription.policy_id,
-        assessor_notes: String::from_str(&env, ""), // Empty string initially
+        assessor_notes: String::from_str(env, ""), // Empty string initially
         payout_date: None,
         status: ClaimStatus::Submitted,
         description,
         claim_type: ClaimType::Standard, // Default to Standard type
+        proposal_id: None,
     };
 
-    env.storage().instance().set(&DataKey::Claim(claim_id), &claim);
+    // Every claim is decided by a DAO vote; the default window always fits the type's config
+    let proposal_id = open_claim_vote(env, &claim).map_err(|_| ClaimsProcessingError::InvalidClaimData)?;
+    let claim = Claim { proposal_id: Some(proposal_id), ..claim };
+    storage::save(env, &claim);
 
     env.events().publish(
         (CLAIM_SUBMITTED, claim_id),
@@ -72,32 +88,50 @@ pub fn create_claim(
     Ok(claim_id)
 }
 
-pub fn review_claim(
+// Applies the outcome of a claim's own DAO vote: approved if it passed, rejected otherwise.
+// A claim that is no longer pending is left as it is.
+pub fn settle_claim_vote(env: &Env, claim_id: u64, outcome: &ProposalStatus) {
+    let Ok(claim) = get_claim(env, claim_id) else {
+        return;
+    };
+    if !is_claim_pending(&claim) {
+        return;
+    }
+
+    let (status, notes) = match outcome {
+        ProposalStatus::Passed => (ClaimStatus::Approved, "approved by DAO vote"),
+        ProposalStatus::Expired => (ClaimStatus::Rejected, "vote closed without quorum"),
+        ProposalStatus::Vetoed => (ClaimStatus::Rejected, "vetoed by the council"),
+        _ => (ClaimStatus::Rejected, "rejected by DAO vote"),
+    };
+    record_resolution(env, claim, env.current_contract_address(), status, String::from_str(env, notes));
+}
+
+// Records a claim decision.
+pub fn execute_claim_resolution(
     env: &Env,
     claim_id: u64,
     reviewer: Address,
     status: ClaimStatus,
     notes: String,
 ) -> Result<bool, ClaimsProcessingError> {
-    let claim_key = DataKey::Claim(claim_id);
-    let mut claim = env.storage().instance().get::<_, Claim>(&claim_key)
-        .ok_or(ClaimsProcessingError::ClaimNotFound)?;
-
-    // Check if reviewer is authorized (DAO member or admin)
-    if !is_user_approved(env, &reviewer) {
-        return Err(ClaimsProcessingError::Unauthorized);
-    }
+    let claim = get_claim(env, claim_id)?;
 
     // Check if claim can be reviewed
-    if claim.status != ClaimStatus::Submitted {
+    if !is_claim_pending(&claim) {
         return Err(ClaimsProcessingError::ClaimAlreadyProcessed);
     }
 
+    record_resolution(env, claim, reviewer, status, notes);
+    Ok(true)
+}
+
+fn record_resolution(env: &Env, mut claim: Claim, reviewer: Address, status: ClaimStatus, notes: String) {
     // Update claim status
     claim.status = status.clone();
     claim.assessor_notes = notes;
 
-    env.storage().instance().set(&claim_key, &claim);
+    storage::save(env, &claim);
 
     let event_type = match status {
         ClaimStatus::Approved => CLAIM_APPROVED,
@@ -106,11 +140,9 @@ pub fn review_claim(
     };
 
     env.events().publish(
-        (event_type, claim_id),
+        (event_type, claim.id),
         (reviewer, status, claim.amount)
     );
-
-    Ok(true)
 }
 
 pub fn process_claim_payout(
@@ -118,10 +150,16 @@ pub fn process_claim_payout(
     claim_id: u64,
     processor: Address,
 ) -> Result<bool, ClaimsProcessingError> {
+    processor.require_auth();
     let claim_key = DataKey::Claim(claim_id);
-    let mut claim = env.storage().instance().get::<_, Claim>(&claim_key)
+    let mut claim = storage::load::<Claim>(env, &claim_key)
         .ok_or(ClaimsProcessingError::ClaimNotFound)?;
 
+    // A claim can only ever be paid once
+    if claim.status == ClaimStatus::Paid {
+        return Err(ClaimsProcessingError::ClaimAlreadyPaid);
+    }
+
     // Check if claim is approved
     if claim.status != ClaimStatus::Approved {
         return Err(ClaimsProcessingError::ClaimAlreadyProcessed);
@@ -133,7 +171,7 @@ pub fn process_claim_payout(
     }
 
     // Get the policy to validate coverage
-    let policy = env.storage().instance().get::<_, Policy>(&DataKey::Policy(claim.plan_id))
+    let policy = storage::load::<Policy>(env, &DataKey::Policy(claim.plan_id))
         .ok_or(ClaimsProcessingError::PolicyNotFound)?;
 
     // Check if policy is active
@@ -146,12 +184,29 @@ pub fn process_claim_payout(
         return Err(ClaimsProcessingError::InsufficientCoverage);
     }
 
-    // Process payout
+    if FinancialManagementService::is_fund_frozen(env) {
+        return Err(ClaimsProcessingError::FundFrozen);
+    }
+
+    // Payouts may not dip into the minimum reserve
+    if FinancialManagementService::calculate_claim_capacity(env) < claim.amount {
+        return Err(ClaimsProcessingError::InsufficientReserves);
+    }
+
+    let token_address = FinancialManagementService::get_premium_token(env)
+        .ok_or(ClaimsProcessingError::PaymentFailed)?;
+
+    // Mark the claim paid before moving funds
     claim.status = ClaimStatus::Paid;
     claim.payout_date = Some(env.ledger().timestamp());
+    storage::save(env, &claim);
+    FinancialManagementService::record_claim_payout(env, &claim);
 
-    // Update claim
-    env.storage().instance().set(&claim_key, &claim);
+    token::Client::new(env, &token_address).transfer(
+        &env.current_contract_address(),
+        &claim.claimer,
+        &claim.amount,
+    );
 
     env.events().publish(
         (CLAIM_PAID, claim_id),
@@ -166,10 +221,11 @@ pub fn update_claim(
     claim_id: u64,
     updater: Address,
     description: Option<String>,
-    new_image_hash: Option<String>,
+    new_image_hash: Option<BytesN<32>>,
 ) -> Result<bool, ClaimsProcessingError> {
+    updater.require_auth();
     let claim_key = DataKey::Claim(claim_id);
-    let mut claim = env.storage().instance().get::<_, Claim>(&claim_key)
+    let mut claim = storage::load::<Claim>(env, &claim_key)
         .ok_or(ClaimsProcessingError::ClaimNotFound)?;
 
     // Check if updater is the claimer
@@ -184,15 +240,17 @@ pub fn update_claim(
 
     // Update fields if provided
     if let Some(desc) = description {
-        // Note: The current Claim struct doesn't have a description field
-        // This would need to be added to the state if needed
+        claim.description = desc;
     }
 
     if let Some(image_hash) = new_image_hash {
-        claim.image_hash = image_hash.as_object().to_xdr(&env).try_into().unwrap();
+        claim.image_hash = image_hash;
     }
 
-    env.storage().instance().set(&claim_key, &claim);
+    // Votes cast on the old evidence are discarded with the vote they were cast on
+    let proposal_id = restart_claim_vote(env, &claim).map_err(|_| ClaimsProcessingError::ClaimAlreadyProcessed)?;
+    claim.proposal_id = Some(proposal_id);
+    storage::save(env, &claim);
 
     env.events().publish(
         (CLAIM_SUBMITTED, claim_id),
@@ -203,63 +261,11 @@ pub fn update_claim(
 }
 
 pub fn get_claim(env: &Env, claim_id: u64) -> Result<Claim, ClaimsProcessingError> {
-    env.storage().instance().get(&DataKey::Claim(claim_id))
+    storage::load(env, &DataKey::Claim(claim_id))
         .ok_or(ClaimsProcessingError::ClaimNotFound)
 }
 
-pub fn get_user_claims(env: &Env, user: Address) -> Result<SdkVec<u64>, ClaimsProcessingError> {
-    // This is a simplified implementation - in a real contract you might want to maintain an index
-    // For now, we'll return an empty vector as this would require more complex storage patterns
-    Ok(SdkVec::new(&env))
-}
-
-pub fn get_subscription_claims(env: &Env, subscription_id: u64) -> Result<SdkVec<u64>, ClaimsProcessingError> {
-    // This is a simplified implementation - in a real contract you might want to maintain an index
-    // For now, we'll return an empty vector as this would require more complex storage patterns
-    Ok(SdkVec::new(&env))
-}
-
-pub fn get_pending_claims(env: &Env) -> Result<SdkVec<u64>, ClaimsProcessingError> {
-    // This is a simplified implementation - in a real contract you might want to maintain an index
-    // For now, we'll return an empty vector as this would require more complex storage patterns
-    Ok(SdkVec::new(&env))
-}
-
-// Helper function to calculate risk score for a claim
-pub fn calculate_claim_risk_score(
-    claim_amount: i128,
-    policy_max_claim: i128,
-    claimant_history: u32,
-) -> u32 {
-    let mut risk_score = 0;
-
-    // Amount risk (higher amount = higher risk)
-    let amount_ratio = (claim_amount * 100) / policy_max_claim;
-    if amount_ratio > 80 {
-        risk_score += 30;
-    } else if amount_ratio > 50 {
-        risk_score += 20;
-    } else if amount_ratio > 20 {
-        risk_score += 10;
-    }
-
-    // Claimant history risk
-    if claimant_history > 5 {
-        risk_score += 25;
-    } else if claimant_history > 2 {
-        risk_score += 15;
-    }
-
-    risk_score.min(100) // Cap at 100
-}
-
-// Helper function to check if a claim can be appealed
-pub fn can_appeal_claim(env: &Env, claim: &Claim) -> bool {
-    claim.status == ClaimStatus::Rejected
-}
-
-// Helper function to check if a claim is overdue for review
-pub fn is_claim_overdue(env: &Env, claim: &Claim) -> bool {
-    claim.status == ClaimStatus::UnderReview && 
-    env.ledger().timestamp() > claim.created_at + (30 * 24 * 60 * 60) // 30 days overdue
+// Still awaiting a decision, whether or not assessors have been assigned
+pub fn is_claim_pending(claim: &Claim) -> bool {
+    matches!(claim.status, ClaimStatus::Submitted | ClaimStatus::UnderReview)
 }
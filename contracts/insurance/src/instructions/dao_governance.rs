@@ -1,19 +1,59 @@
-use soroban_sdk::{Env, Address, String, Vec as SdkVec};
-use crate::constant::{PROPOSAL_CREATED, VOTE_CAST, PROPOSAL_EXECUTED};
-use crate::state::{DataKey, Proposal, ProposalType, ProposalStatus, User, DAOVote};
+use soroban_sdk::{Bytes, Env, Address, String, Vec as SdkVec};
+use crate::constant::{
+    PROPOSAL_CREATED, VOTE_CAST, PROPOSAL_FINALIZED, PROPOSAL_CONFIG_UPDATED, SECONDS_PER_DAY,
+    BASE_QUORUM_MULTIPLIER, DELEGATION_OVERRIDDEN, PROPOSAL_CANCELLED, VETO_CAST, PROPOSAL_VETOED,
+    VETO_THRESHOLD, PROPOSAL_QUEUED, TIMELOCK_BYPASS_THRESHOLD, MAX_PAGE_SIZE, CLAIM_SMALL_AMOUNT, CLAIM_MEDIUM_AMOUNT,
+    CLAIM_MEDIUM_QUORUM_MULTIPLIER, CLAIM_LARGE_QUORUM_MULTIPLIER,
+};
+use crate::state::{
+    AssessmentSummary, Claim, ClaimVote, DataKey, ListKey, Proposal, ProposalConfig, ProposalListing, ProposalPage, ProposalType, ProposalStatus, ProxyVote,
+    TallyMode, User, UserStatus, VoteKey, VoteRecord,
+};
+use crate::instructions::claims_processing::settle_claim_vote;
+use crate::instructions::delegation::{is_proxied, release_proxy, represented_members};
+use crate::instructions::secret_ballot::{is_sealed, tally_revealed_votes};
+use crate::instructions::tally::{apply_conviction, counted_weight};
+use crate::instructions::user_management::{get_council_members, get_vote_weight, is_council_member};
+use crate::instructions::financial_management::FinancialManagementService;
+use crate::instructions::proposal_execution::decode_action;
+use crate::storage::{self, next_id};
 
 #[derive(Debug)]
 pub enum DAOGovernanceError {
     ProposalNotFound,
-    ProposalAlreadyExecuted,
     ProposalNotOpen,
     VotingPeriodEnded,
     AlreadyVoted,
     Unauthorized,
-    InsufficientQuorum,
     InvalidVoteType,
+    InvalidProposalPayload,
+    VoteNotFound,
+    InvalidVotingPeriod,
+    InvalidProposalConfig,
+    InsufficientReputation,
+    VotingStillOpen,
+    DelegationNotFound,
+    InvalidDelegation,
+    DelegationCycle,
+    VotesAlreadyCast,
+    AlreadyVetoed,
+    TimelockActive,
+    RevealMismatch,
 }
 
+pub const PROPOSAL_TYPES: [ProposalType; 10] = [
+    ProposalType::PolicyCreation,
+    ProposalType::PolicyArchival,
+    ProposalType::PolicyDeletion,
+    ProposalType::UserApproval,
+    ProposalType::UserBan,
+    ProposalType::GovernanceUpdate,
+    ProposalType::FinancialDecision,
+    ProposalType::ClaimResolution,
+    ProposalType::EmergencyAction,
+    ProposalType::MembershipChange,
+];
+
 pub fn create_proposal(
     env: &Env,
     proposer: Address,
@@ -21,19 +61,38 @@ pub fn create_proposal(
     title: String,
     description: String,
     execution_data: soroban_sdk::Bytes,
-    required_quorum: u32,
+    voting_duration: Option<u64>,
 ) -> Result<u64, DAOGovernanceError> {
+    proposer.require_auth();
     // Check if proposer is DAO member
-    let user = env.storage().instance().get::<_, User>(&DataKey::User(proposer.clone()))
-        .ok_or(DAOGovernanceError::Unauthorized)?;
-    
-    if !user.is_dao_member {
+    if !is_council_member(env, &proposer) {
         return Err(DAOGovernanceError::Unauthorized);
     }
+    // Reject payloads that could never execute before anyone votes on them
+    decode_action(env, &proposal_type, &execution_data)?;
 
-    let proposal_id = env.ledger().sequence() as u64;
-    let voting_period = 604800; // 7 days in seconds
+    open_proposal(env, proposer, proposal_type, title, description, execution_data, voting_duration)
+}
+
+// Stores a new open proposal using the quorum, window and threshold of its type's config.
+// `voting_duration` defaults to the config's minimum. Callers are responsible for authorization.
+pub fn open_proposal(
+    env: &Env,
+    proposer: Address,
+    proposal_type: ProposalType,
+    title: String,
+    description: String,
+    execution_data: soroban_sdk::Bytes,
+    voting_duration: Option<u64>,
+) -> Result<u64, DAOGovernanceError> {
+    let config = get_proposal_config(env, proposal_type.clone());
+    let voting_period = voting_duration.unwrap_or(config.min_duration);
+    if voting_period < config.min_duration || voting_period > config.max_duration {
+        return Err(DAOGovernanceError::InvalidVotingPeriod);
+    }
+    let quorum = required_quorum(env, &config);
 
+    let proposal_id = next_id(env, DataKey::LatestProposalId);
     let proposal = Proposal {
         id: proposal_id,
         proposer: proposer.clone(),
@@ -46,16 +105,31 @@ pub fn create_proposal(
         no_votes: 0,
         status: ProposalStatus::Open,
         execution_data,
-        required_quorum,
-        voters: SdkVec::new(&env),
+        required_quorum: quorum,
+        voters: SdkVec::new(env),
         voting_period_end: env.ledger().timestamp() + voting_period,
         votes_for: 0,
         votes_against: 0,
-        quorum_required: required_quorum as i128,
+        delegated_for: 0,
+        delegated_against: 0,
+        quorum_required: quorum as i128,
         created_date: env.ledger().timestamp(),
+        approval_threshold: config.approval_threshold,
+        executed_by: None,
+        executed_at: None,
+        status_reason: None,
+        eta: None,
+        reveal_end: (config.reveal_duration > 0)
+            .then(|| env.ledger().timestamp() + voting_period + config.reveal_duration),
+        tally_mode: config.tally_mode,
+        claim_id: None,
+        assessment: AssessmentSummary::default(),
     };
 
-    env.storage().instance().set(&DataKey::DAOProposal(proposal_id), &proposal);
+    storage::save(env, &proposal);
+    storage::list_push(env, &ListKey::ProposalsByType(proposal_type.clone()), &proposal_id);
+    storage::list_push(env, &ListKey::ProposalsByProposer(proposer.clone()), &proposal_id);
+    add_to_listing(env, proposal_id, ProposalListing::Open);
     
     env.events().publish(
         (PROPOSAL_CREATED, proposal_id),
@@ -65,63 +139,205 @@ pub fn create_proposal(
     Ok(proposal_id)
 }
 
+// Opens the ClaimResolution vote for a newly submitted claim, with the claimer as proposer.
+// Quorum is the type's, scaled up for larger claims, but never more than the council can cast
+// without the claimer. The caller links the claim to the vote.
+pub fn open_claim_vote(env: &Env, claim: &Claim) -> Result<u64, DAOGovernanceError> {
+    let proposal_id = open_proposal(
+        env,
+        claim.claimer.clone(),
+        ProposalType::ClaimResolution,
+        String::from_str(env, "Claim resolution"),
+        claim.description.clone(),
+        Bytes::new(env),
+        None,
+    )?;
+
+    let multiplier = if claim.amount <= CLAIM_SMALL_AMOUNT {
+        BASE_QUORUM_MULTIPLIER
+    } else if claim.amount <= CLAIM_MEDIUM_AMOUNT {
+        CLAIM_MEDIUM_QUORUM_MULTIPLIER
+    } else {
+        CLAIM_LARGE_QUORUM_MULTIPLIER
+    };
+    let mut proposal = get_proposal(env, proposal_id)?;
+    let scaled = (proposal.required_quorum * multiplier).div_ceil(BASE_QUORUM_MULTIPLIER);
+    proposal.required_quorum = scaled.min(castable_weight(env, &proposal, &claim.claimer)).max(1);
+    proposal.quorum_required = proposal.required_quorum as i128;
+    proposal.claim_id = Some(claim.id);
+    storage::save(env, &proposal);
+    Ok(proposal_id)
+}
+
+// Starts a claim's vote over after the claimer changed its evidence, so no vote counts for a
+// version of the claim it was not cast on. The old vote must still be running; it is cancelled
+// with the votes on it, and the caller links the claim to the new one.
+pub fn restart_claim_vote(env: &Env, claim: &Claim) -> Result<u64, DAOGovernanceError> {
+    if let Some(old_id) = claim.proposal_id {
+        let mut old = get_proposal(env, old_id)?;
+        if old.status != ProposalStatus::Open || env.ledger().timestamp() > old.voting_period_end {
+            return Err(DAOGovernanceError::ProposalNotOpen);
+        }
+        set_status(env, &mut old, ProposalStatus::Cancelled);
+        old.status_reason = Some(String::from_str(env, "claim updated"));
+        storage::save(env, &old);
+        env.events().publish((PROPOSAL_CANCELLED, old_id), claim.claimer.clone());
+    }
+    open_claim_vote(env, claim)
+}
+
+// Weighted votes needed for quorum: the platform's minimum quorum scaled by the type's multiplier
+fn required_quorum(env: &Env, config: &ProposalConfig) -> u32 {
+    let base = FinancialManagementService::get_platform_config(env).minimum_quorum;
+    (base * config.quorum_multiplier).div_ceil(BASE_QUORUM_MULTIPLIER).max(1)
+}
+
+pub fn default_proposal_config(proposal_type: ProposalType) -> ProposalConfig {
+    // (quorum multiplier %, min days, max days, min voter reputation, approval threshold bps, timelock days)
+    let (quorum_multiplier, min_days, max_days, min_voter_reputation, approval_threshold, timelock_days) = match proposal_type {
+        ProposalType::PolicyCreation
+        | ProposalType::PolicyArchival
+        | ProposalType::PolicyDeletion => (100, 7, 14, 0, 5000, 0),
+        ProposalType::UserApproval => (100, 3, 7, 0, 5000, 0),
+        ProposalType::UserBan | ProposalType::MembershipChange => (100, 7, 14, 0, 6000, 0),
+        ProposalType::ClaimResolution => (100, 3, 3, 0, 6000, 0),
+        // 130% of the default quorum of 3 is 4, which a full default council of 5 can still reach
+        ProposalType::FinancialDecision => (130, 7, 14, 0, 6000, 2),
+        ProposalType::GovernanceUpdate => (130, 7, 14, 50, 6667, 2),
+        // Emergencies skip the queue; the higher threshold is the safeguard instead
+        ProposalType::EmergencyAction => (130, 1, 3, 0, 7500, 0),
+    };
+
+    ProposalConfig {
+        proposal_type,
+        quorum_multiplier,
+        min_duration: min_days * SECONDS_PER_DAY,
+        max_duration: max_days * SECONDS_PER_DAY,
+        min_voter_reputation,
+        approval_threshold,
+        timelock_delay: timelock_days * SECONDS_PER_DAY,
+        reveal_duration: 0,
+        tally_mode: TallyMode::Linear,
+    }
+}
+
+pub fn get_proposal_config(env: &Env, proposal_type: ProposalType) -> ProposalConfig {
+    storage::load(env, &DataKey::ProposalConfig(proposal_type.clone()))
+        .unwrap_or_else(|| default_proposal_config(proposal_type))
+}
+
+// Stores the default config for every proposal type; run once at initialization.
+pub fn seed_proposal_configs(env: &Env) {
+    for proposal_type in PROPOSAL_TYPES {
+        storage::save(env, &default_proposal_config(proposal_type));
+    }
+}
+
+// Replaces one type's rules. Only reachable through an executed `GovernanceUpdate` proposal.
+// Proposals already open keep the quorum and threshold they were created with.
+pub fn execute_proposal_config_update(env: &Env, config: ProposalConfig) -> Result<bool, DAOGovernanceError> {
+    if config.quorum_multiplier == 0
+        || config.min_duration == 0
+        || config.min_duration > config.max_duration
+        || config.approval_threshold == 0
+        || config.approval_threshold > 10000
+    {
+        return Err(DAOGovernanceError::InvalidProposalConfig);
+    }
+    if is_sensitive(&config.proposal_type)
+        && config.timelock_delay == 0
+        && config.approval_threshold < TIMELOCK_BYPASS_THRESHOLD
+    {
+        return Err(DAOGovernanceError::InvalidProposalConfig);
+    }
+    // Conviction is measured from when votes were cast, which a secret ballot only learns at reveal
+    if config.tally_mode == TallyMode::Conviction && config.reveal_duration > 0 {
+        return Err(DAOGovernanceError::InvalidProposalConfig);
+    }
+
+    storage::save(env, &config);
+    env.events().publish(
+        (PROPOSAL_CONFIG_UPDATED, config.proposal_type.clone()),
+        (config.approval_threshold, config.min_duration, config.max_duration)
+    );
+    Ok(true)
+}
+
 pub fn vote_on_proposal(
     env: &Env,
     proposal_id: u64,
     voter: Address,
     vote_direction: bool,
-    vote_weight: i128,
+    metadata: Option<String>,
 ) -> Result<bool, DAOGovernanceError> {
+    voter.require_auth();
     let proposal_key = DataKey::DAOProposal(proposal_id);
-    let mut proposal = env.storage().instance().get::<_, Proposal>(&proposal_key)
+    let mut proposal = storage::load::<Proposal>(env, &proposal_key)
         .ok_or(DAOGovernanceError::ProposalNotFound)?;
 
-    if proposal.status != ProposalStatus::Open {
-        return Err(DAOGovernanceError::ProposalNotOpen);
+    ensure_can_vote(env, &proposal, &voter)?;
+    // Secret ballots take a commitment instead (see `secret_ballot::commit_vote`)
+    if proposal.reveal_end.is_some() {
+        return Err(DAOGovernanceError::InvalidVoteType);
     }
 
-    if env.ledger().timestamp() > proposal.voting_period_end {
-        return Err(DAOGovernanceError::VotingPeriodEnded);
-    }
+    // Weight comes from the member's cached reputation/stake, never from the caller
+    let weight = get_vote_weight(env, voter.clone())
+        .map_err(|_| DAOGovernanceError::Unauthorized)?;
 
-    // Check if user has already voted
-    if proposal.voters.contains(&voter) {
-        return Err(DAOGovernanceError::AlreadyVoted);
+    // A direct vote overrides a delegate who already voted on this member's behalf
+    if let Some(proxy) = release_proxy(env, &mut proposal, &voter) {
+        env.events().publish(
+            (DELEGATION_OVERRIDDEN, proposal_id),
+            (voter.clone(), proxy.representative)
+        );
     }
 
-    // Check if voter is DAO member
-    let user = env.storage().instance().get::<_, User>(&DataKey::User(voter.clone()))
-        .ok_or(DAOGovernanceError::Unauthorized)?;
-    
-    if !user.is_dao_member {
-        return Err(DAOGovernanceError::Unauthorized);
+    // Carry the weight of delegators who have not voted themselves
+    let mut delegated_weight = 0;
+    for (member, member_weight) in represented_members(env, &proposal, &voter).iter() {
+        release_proxy(env, &mut proposal, &member);
+        storage::save(env, &ProxyVote {
+            proposal_id,
+            member,
+            representative: voter.clone(),
+            weight: member_weight,
+            vote: vote_direction,
+        });
+        delegated_weight += member_weight;
     }
+    let vote_weight = counted_weight(&weight, &proposal.tally_mode) + delegated_weight;
 
     // Record the vote
-    let vote = DAOVote {
+    let vote = VoteRecord {
         proposal_id,
         voter: voter.clone(),
-        vote_weight,
-        vote_direction,
+        weight,
+        vote: vote_direction,
         timestamp: env.ledger().timestamp(),
+        metadata,
+        delegated_weight,
     };
 
     // Update proposal vote counts
     if vote_direction {
-        proposal.votes_for += vote_weight as u32;
+        proposal.votes_for += vote_weight;
+        proposal.delegated_for += delegated_weight;
         proposal.yes_votes += 1;
     } else {
-        proposal.votes_against += vote_weight as u32;
+        proposal.votes_against += vote_weight;
+        proposal.delegated_against += delegated_weight;
         proposal.no_votes += 1;
     }
 
     proposal.voters.push_back(voter.clone());
     
-    // Store the vote
-    env.storage().instance().set(&DataKey::DAOVote(proposal_id), &vote);
+    // Store the vote and index it under the voter's history
+    storage::save(env, &vote);
+    storage::list_push(env, &ListKey::MemberVotes(voter.clone()), &proposal_id);
     
     // Update proposal
-    env.storage().instance().set(&proposal_key, &proposal);
+    storage::save(env, &proposal);
 
     env.events().publish(
         (VOTE_CAST, proposal_id),
@@ -131,116 +347,403 @@ pub fn vote_on_proposal(
     Ok(true)
 }
 
-pub fn finalize_proposal(env: &Env, proposal_id: u64) -> Result<bool, DAOGovernanceError> {
-    let proposal_key = DataKey::DAOProposal(proposal_id);
-    let mut proposal = env.storage().instance().get::<_, Proposal>(&proposal_key)
-        .ok_or(DAOGovernanceError::ProposalNotFound)?;
-
+// Checks the proposal is taking votes and the voter is a member who has not voted yet
+pub fn ensure_can_vote(env: &Env, proposal: &Proposal, voter: &Address) -> Result<(), DAOGovernanceError> {
     if proposal.status != ProposalStatus::Open {
-        return Err(DAOGovernanceError::ProposalAlreadyExecuted);
+        return Err(DAOGovernanceError::ProposalNotOpen);
     }
 
-    if env.ledger().timestamp() <= proposal.voting_period_end {
+    if env.ledger().timestamp() > proposal.voting_period_end {
         return Err(DAOGovernanceError::VotingPeriodEnded);
     }
 
-    let total_votes = proposal.votes_for + proposal.votes_against;
-    let quorum_met = total_votes >= proposal.quorum_required as u32;
+    // Check if user has already voted
+    if proposal.voters.contains(voter) {
+        return Err(DAOGovernanceError::AlreadyVoted);
+    }
 
-    if !quorum_met {
-        proposal.status = ProposalStatus::Failed;
-        env.storage().instance().set(&proposal_key, &proposal);
-        return Ok(false);
+    // Check if voter is DAO member
+    let user = storage::load::<User>(env, &DataKey::User(voter.clone()))
+        .ok_or(DAOGovernanceError::Unauthorized)?;
+    
+    if !user.is_dao_member || user.status != UserStatus::Active {
+        return Err(DAOGovernanceError::Unauthorized);
+    }
+    if proposal.claim_id.is_some() && proposal.proposer == *voter {
+        return Err(DAOGovernanceError::Unauthorized);
     }
 
-    // Determine if proposal passed
-    if proposal.votes_for > proposal.votes_against {
-        proposal.status = ProposalStatus::Passed;
-    } else {
-        proposal.status = ProposalStatus::Rejected;
+    if user.reputation_score < get_proposal_config(env, proposal.proposal_type.clone()).min_voter_reputation {
+        return Err(DAOGovernanceError::InsufficientReputation);
     }
+    Ok(())
+}
 
-    env.storage().instance().set(&proposal_key, &proposal);
+// Settles an open proposal; anyone may call it. Before the window closes it only resolves
+// when the remaining eligible weight can no longer change the outcome. A claim vote does not
+// settle early while an assigned assessor has yet to report; once its window closes, missing
+// reports no longer hold it up.
+pub fn finalize_proposal(env: &Env, proposal_id: u64) -> Result<ProposalStatus, DAOGovernanceError> {
+    let mut proposal = get_proposal(env, proposal_id)?;
+
+    if proposal.status != ProposalStatus::Open {
+        return Err(DAOGovernanceError::ProposalNotOpen);
+    }
+    let assessed = proposal.assessment.approvals + proposal.assessment.rejections;
+    let awaiting_assessment = proposal.claim_id.is_some() && assessed < proposal.assessment.assessors;
+
+    let status = if env.ledger().timestamp() > voting_closes_at(&proposal) {
+        // Secret ballots are only counted once the reveal window has closed
+        if proposal.reveal_end.is_some() {
+            tally_revealed_votes(env, &mut proposal);
+        }
+        // Quorum is turnout, so it is measured before conviction scales the tallies
+        let turnout = proposal.votes_for + proposal.votes_against;
+        if proposal.tally_mode == TallyMode::Conviction {
+            apply_conviction(env, &mut proposal);
+        }
+        closed_outcome(&proposal, turnout)
+    } else if proposal.reveal_end.is_none() && proposal.tally_mode != TallyMode::Conviction && !awaiting_assessment {
+        // Conviction keeps growing until the close, so only open linear or quadratic
+        // tallies can settle early, and claim votes only once every assessor has reported
+        early_outcome(env, &proposal).ok_or(DAOGovernanceError::VotingStillOpen)?
+    } else {
+        return Err(DAOGovernanceError::VotingStillOpen);
+    };
+
+    // Claim votes take effect as soon as they settle; other types with a timelock wait in
+    // the queue before they can be executed
+    let timelock_delay = get_proposal_config(env, proposal.proposal_type.clone()).timelock_delay;
+    let status = if let Some(claim_id) = proposal.claim_id {
+        settle_claim_vote(env, claim_id, &status);
+        if status == ProposalStatus::Passed {
+            proposal.executed_at = Some(env.ledger().timestamp());
+            ProposalStatus::Executed
+        } else {
+            status
+        }
+    } else if status == ProposalStatus::Passed && timelock_delay > 0 {
+        proposal.eta = Some(env.ledger().timestamp() + timelock_delay);
+        ProposalStatus::Queued
+    } else {
+        status
+    };
+
+    set_status(env, &mut proposal, status.clone());
+    storage::save(env, &proposal);
 
     env.events().publish(
-        (PROPOSAL_EXECUTED, proposal_id),
-        (proposal.status, total_votes)
+        (PROPOSAL_FINALIZED, proposal_id),
+        (status.clone(), proposal.votes_for, proposal.votes_against)
     );
+    if let Some(eta) = proposal.eta {
+        env.events().publish((PROPOSAL_QUEUED, proposal_id), eta);
+    }
 
-    Ok(true)
+    Ok(status)
 }
 
-pub fn execute_proposal(env: &Env, proposal_id: u64, executor: Address) -> Result<bool, DAOGovernanceError> {
-    let proposal_key = DataKey::DAOProposal(proposal_id);
-    let proposal = env.storage().instance().get::<_, Proposal>(&proposal_key)
-        .ok_or(DAOGovernanceError::ProposalNotFound)?;
+// Changes a proposal's status and moves it between the status listings. The caller saves it.
+pub fn set_status(env: &Env, proposal: &mut Proposal, status: ProposalStatus) {
+    let (from, to) = (proposal.status.listing(), status.listing());
+    proposal.status = status;
+    if from == to {
+        return;
+    }
+
+    if let Some(position) = storage::list_slot(env, &DataKey::ListingSlot(proposal.id)) {
+        let moved = storage::list_swap_remove::<u64>(env, &ListKey::ProposalsByStatus(from), position);
+        if let Some(moved_id) = moved {
+            storage::set_list_slot(env, &DataKey::ListingSlot(moved_id), position);
+        }
+    }
+    add_to_listing(env, proposal.id, to);
+}
+
+fn add_to_listing(env: &Env, proposal_id: u64, listing: ProposalListing) {
+    let position = storage::list_push(env, &ListKey::ProposalsByStatus(listing), &proposal_id);
+    storage::set_list_slot(env, &DataKey::ListingSlot(proposal_id), position);
+}
+
+// Types that move treasury funds or change the rules
+fn is_sensitive(proposal_type: &ProposalType) -> bool {
+    matches!(
+        proposal_type,
+        ProposalType::FinancialDecision | ProposalType::GovernanceUpdate | ProposalType::EmergencyAction
+    )
+}
+
+// Finalizes every listed proposal whose voting window has closed. Proposals that are
+// still running, already settled or missing are skipped. Returns how many were settled.
+pub fn sweep_expired_proposals(env: &Env, proposal_ids: SdkVec<u64>) -> u32 {
+    let mut settled = 0;
+    for proposal_id in proposal_ids.iter() {
+        let closed = match get_proposal(env, proposal_id) {
+            Ok(proposal) => {
+                proposal.status == ProposalStatus::Open
+                    && env.ledger().timestamp() > voting_closes_at(&proposal)
+            }
+            Err(_) => false,
+        };
+        if closed && finalize_proposal(env, proposal_id).is_ok() {
+            settled += 1;
+        }
+    }
+    settled
+}
+
+// Lets the proposer withdraw a proposal nobody has voted on yet
+pub fn cancel_proposal(env: &Env, proposal_id: u64, proposer: Address) -> Result<bool, DAOGovernanceError> {
+    proposer.require_auth();
+    let mut proposal = get_proposal(env, proposal_id)?;
 
-    if proposal.status != ProposalStatus::Passed {
+    // A claimer cannot withdraw the vote on their own claim
+    if proposal.proposer != proposer || proposal.claim_id.is_some() {
+        return Err(DAOGovernanceError::Unauthorized);
+    }
+    if proposal.status != ProposalStatus::Open {
         return Err(DAOGovernanceError::ProposalNotOpen);
     }
+    if !proposal.voters.is_empty() {
+        return Err(DAOGovernanceError::VotesAlreadyCast);
+    }
 
-    // Check if executor is DAO member
-    let user = env.storage().instance().get::<_, User>(&DataKey::User(executor.clone()))
-        .ok_or(DAOGovernanceError::Unauthorized)?;
-    
-    if !user.is_dao_member {
+    set_status(env, &mut proposal, ProposalStatus::Cancelled);
+    storage::save(env, &proposal);
+
+    env.events().publish((PROPOSAL_CANCELLED, proposal_id), proposer);
+    Ok(true)
+}
+
+// Records a council member's veto against an open, passed or queued proposal. Once members holding
+// VETO_THRESHOLD of the active council have vetoed, the proposal is blocked for good and the
+// reason given by the deciding veto is kept on it. Returns the proposal's status afterwards.
+pub fn veto_proposal(
+    env: &Env,
+    proposal_id: u64,
+    member: Address,
+    reason: String,
+) -> Result<ProposalStatus, DAOGovernanceError> {
+    member.require_auth();
+    let mut proposal = get_proposal(env, proposal_id)?;
+
+    if !matches!(proposal.status, ProposalStatus::Open | ProposalStatus::Passed | ProposalStatus::Queued) {
+        return Err(DAOGovernanceError::ProposalNotOpen);
+    }
+    if !is_council_member(env, &member) {
         return Err(DAOGovernanceError::Unauthorized);
     }
 
-    // Mark proposal as active (since Executed doesn't exist)
-    let mut updated_proposal = proposal.clone();
-    updated_proposal.status = ProposalStatus::Active;
-    env.storage().instance().set(&proposal_key, &updated_proposal);
+    let vetoes_key = DataKey::Vetoes(proposal_id);
+    let mut vetoes = storage::load_index::<Address>(env, &vetoes_key);
+    if vetoes.contains(&member) {
+        return Err(DAOGovernanceError::AlreadyVetoed);
+    }
+    vetoes.push_back(member.clone());
+    storage::save_index(env, &vetoes_key, &vetoes);
 
     env.events().publish(
-        (PROPOSAL_EXECUTED, proposal_id),
-        (executor, "executed")
+        (VETO_CAST, proposal_id),
+        (member, reason.clone(), vetoes.len())
     );
 
-    Ok(true)
+    // Vetoes are counted per head: a supermajority of members, not of vote weight
+    let active_council = get_council_members(env)
+        .iter()
+        .filter(|address| is_council_member(env, address))
+        .count() as u64;
+    if vetoes.len() as u64 * 10000 >= VETO_THRESHOLD as u64 * active_council {
+        set_status(env, &mut proposal, ProposalStatus::Vetoed);
+        proposal.status_reason = Some(reason.clone());
+        storage::save(env, &proposal);
+        if let Some(claim_id) = proposal.claim_id {
+            settle_claim_vote(env, claim_id, &ProposalStatus::Vetoed);
+        }
+        env.events().publish((PROPOSAL_VETOED, proposal_id), (reason, vetoes.len()));
+    }
+
+    Ok(proposal.status)
 }
 
-pub fn get_proposal(env: &Env, proposal_id: u64) -> Result<Proposal, DAOGovernanceError> {
-    env.storage().instance().get(&DataKey::DAOProposal(proposal_id))
-        .ok_or(DAOGovernanceError::ProposalNotFound)
+pub fn get_proposal_vetoes(env: &Env, proposal_id: u64) -> SdkVec<Address> {
+    storage::load_index(env, &DataKey::Vetoes(proposal_id))
+}
+
+// When the last vote can land: the end of the reveal window for secret ballots
+fn voting_closes_at(proposal: &Proposal) -> u64 {
+    proposal.reveal_end.unwrap_or(proposal.voting_period_end)
 }
 
-pub fn get_vote(env: &Env, proposal_id: u64, voter: Address) -> Result<DAOVote, DAOGovernanceError> {
-    env.storage().instance().get(&DataKey::DAOVote(proposal_id))
-        .ok_or(DAOGovernanceError::Unauthorized)
+fn closed_outcome(proposal: &Proposal, turnout: u32) -> ProposalStatus {
+    if turnout < proposal.required_quorum {
+        ProposalStatus::Expired
+    } else if meets_threshold(proposal.votes_for, proposal.votes_for + proposal.votes_against, proposal.approval_threshold) {
+        ProposalStatus::Passed
+    } else {
+        ProposalStatus::Rejected
+    }
 }
 
-pub fn get_proposals_by_type(env: &Env, proposal_type: ProposalType) -> Result<SdkVec<u64>, DAOGovernanceError> {
-    // This is a simplified implementation - in a real contract you might want to maintain an index
-    // For now, we'll return an empty vector as this would require more complex storage patterns
-    Ok(SdkVec::new(&env))
+// Once quorum is met, the result is locked in if it survives every remaining eligible
+// member voting against it (or, for a rejection, every one voting for it). Weight cast by
+// delegates counts as movable, since its owners can still override it.
+fn early_outcome(env: &Env, proposal: &Proposal) -> Option<ProposalStatus> {
+    let total_votes = proposal.votes_for + proposal.votes_against;
+    if total_votes < proposal.required_quorum {
+        return None;
+    }
+
+    let remaining = remaining_eligible_weight(env, proposal);
+    let max_total = total_votes + remaining;
+    let firm_for = proposal.votes_for - proposal.delegated_for;
+    let possible_for = proposal.votes_for + proposal.delegated_against + remaining;
+    if meets_threshold(firm_for, max_total, proposal.approval_threshold) {
+        Some(ProposalStatus::Passed)
+    } else if !meets_threshold(possible_for, max_total, proposal.approval_threshold) {
+        Some(ProposalStatus::Rejected)
+    } else {
+        None
+    }
 }
 
-pub fn get_active_proposals(env: &Env) -> Result<SdkVec<u64>, DAOGovernanceError> {
-    // This is a simplified implementation - in a real contract you might want to maintain an index
-    // For now, we'll return an empty vector as this would require more complex storage patterns
-    Ok(SdkVec::new(&env))
+fn meets_threshold(votes_for: u32, total_votes: u32, approval_threshold: u32) -> bool {
+    votes_for as u64 * 10000 > approval_threshold as u64 * total_votes as u64
 }
 
-// Helper function to check if a proposal can be voted on
-pub fn can_vote_on_proposal(env: &Env, proposal_id: u64, voter: Address) -> bool {
-    if let Ok(proposal) = get_proposal(env, proposal_id) {
-        if proposal.status != ProposalStatus::Open {
-            return false;
+// Current weight of council members who could still vote on the proposal
+fn remaining_eligible_weight(env: &Env, proposal: &Proposal) -> u32 {
+    let mut remaining = 0;
+    for member in get_council_members(env).iter() {
+        if proposal.voters.contains(&member) || is_proxied(env, proposal.id, &member) {
+            continue;
         }
-        
-        if env.ledger().timestamp() > proposal.voting_period_end {
-            return false;
+        remaining += eligible_weight(env, &member, proposal).unwrap_or(0);
+    }
+    remaining
+}
+
+// Weight the whole council could cast on the proposal, leaving out `excluded`
+fn castable_weight(env: &Env, proposal: &Proposal, excluded: &Address) -> u32 {
+    get_council_members(env)
+        .iter()
+        .filter(|member| member != excluded)
+        .map(|member| eligible_weight(env, &member, proposal).unwrap_or(0))
+        .sum()
+}
+
+// The weight a member's vote would count for on this proposal, if they may vote on it
+pub fn eligible_weight(env: &Env, member: &Address, proposal: &Proposal) -> Option<u32> {
+    let user = storage::load::<User>(env, &DataKey::User(member.clone()))?;
+    let min_reputation = get_proposal_config(env, proposal.proposal_type.clone()).min_voter_reputation;
+    if !user.is_dao_member || user.status != UserStatus::Active || user.reputation_score < min_reputation {
+        return None;
+    }
+    get_vote_weight(env, member.clone()).ok().map(|weight| counted_weight(&weight, &proposal.tally_mode))
+}
+
+pub fn get_proposal(env: &Env, proposal_id: u64) -> Result<Proposal, DAOGovernanceError> {
+    storage::load(env, &DataKey::DAOProposal(proposal_id))
+        .ok_or(DAOGovernanceError::ProposalNotFound)
+}
+
+// Votes on a secret ballot stay hidden until its reveal window closes
+pub fn get_vote(env: &Env, proposal_id: u64, voter: Address) -> Result<VoteRecord, DAOGovernanceError> {
+    if is_sealed(env, &get_proposal(env, proposal_id)?) {
+        return Err(DAOGovernanceError::VotingStillOpen);
+    }
+    storage::load(env, &DataKey::Vote(VoteKey { proposal_id, voter }))
+        .ok_or(DAOGovernanceError::VoteNotFound)
+}
+
+// Up to `limit` votes cast on a proposal (never more than MAX_PAGE_SIZE), in the order they
+// were cast, starting at position `start`
+pub fn get_proposal_votes(
+    env: &Env,
+    proposal_id: u64,
+    start: u32,
+    limit: u32,
+) -> Result<SdkVec<VoteRecord>, DAOGovernanceError> {
+    let proposal = get_proposal(env, proposal_id)?;
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(proposal.voters.len());
+    collect_votes(env, &proposal, start, end)
+}
+
+fn collect_votes(
+    env: &Env,
+    proposal: &Proposal,
+    start: u32,
+    end: u32,
+) -> Result<SdkVec<VoteRecord>, DAOGovernanceError> {
+    let mut votes = SdkVec::new(env);
+    for position in start..end {
+        let voter = proposal.voters.get_unchecked(position);
+        match get_vote(env, proposal.id, voter) {
+            Ok(vote) => votes.push_back(vote),
+            // A secret-ballot commitment that was never revealed has no vote to show
+            Err(DAOGovernanceError::VoteNotFound) if proposal.reveal_end.is_some() => {}
+            Err(err) => return Err(err),
         }
-        
-        if proposal.voters.contains(&voter) {
-            return false;
+    }
+    Ok(votes)
+}
+
+// Up to `limit` votes a member has cast (never more than MAX_PAGE_SIZE), oldest first,
+// starting at position `start`. Votes whose records have expired are skipped.
+pub fn get_member_votes(env: &Env, member: Address, start: u32, limit: u32) -> SdkVec<VoteRecord> {
+    let list = ListKey::MemberVotes(member.clone());
+    let mut votes = SdkVec::new(env);
+    for proposal_id in storage::list_page::<u64>(env, &list, start, limit.min(MAX_PAGE_SIZE)).iter() {
+        if let Ok(vote) = get_vote(env, proposal_id, member.clone()) {
+            votes.push_back(vote);
         }
-        
-        // Check if voter is DAO member
-        if let Some(user) = env.storage().instance().get::<_, User>(&DataKey::User(voter)) {
-            return user.is_dao_member;
+    }
+    votes
+}
+
+// The votes on a claim's resolution, as `ClaimVote`s carrying the weight each counted for
+pub fn get_claim_votes(env: &Env, claim: &Claim) -> Result<SdkVec<ClaimVote>, DAOGovernanceError> {
+    let mut votes = SdkVec::new(env);
+    let Some(proposal_id) = claim.proposal_id else {
+        return Ok(votes);
+    };
+    // Claim votes are bounded by the council size, so they are returned whole
+    let proposal = get_proposal(env, proposal_id)?;
+    let tally_mode = proposal.tally_mode.clone();
+    for vote in collect_votes(env, &proposal, 0, proposal.voters.len())?.iter() {
+        votes.push_back(ClaimVote {
+            voter: vote.voter,
+            approve: vote.vote,
+            weight: (counted_weight(&vote.weight, &tally_mode) + vote.delegated_weight) as i128,
+            timestamp: vote.timestamp,
+        });
+    }
+    Ok(votes)
+}
+
+pub fn get_proposals_by_type(env: &Env, proposal_type: ProposalType, start: u32, limit: u32) -> ProposalPage {
+    proposal_page(env, &ListKey::ProposalsByType(proposal_type), start, limit)
+}
+
+pub fn get_proposals_by_status(env: &Env, listing: ProposalListing, start: u32, limit: u32) -> ProposalPage {
+    proposal_page(env, &ListKey::ProposalsByStatus(listing), start, limit)
+}
+
+pub fn get_proposals_by_proposer(env: &Env, proposer: Address, start: u32, limit: u32) -> ProposalPage {
+    proposal_page(env, &ListKey::ProposalsByProposer(proposer), start, limit)
+}
+
+pub fn get_active_proposals(env: &Env, start: u32, limit: u32) -> ProposalPage {
+    get_proposals_by_status(env, ProposalListing::Open, start, limit)
+}
+
+// Up to `limit` proposals (never more than MAX_PAGE_SIZE) from a listing, starting at
+// position `start`. Only the requested page is read. Ids whose records have expired are skipped.
+fn proposal_page(env: &Env, list: &ListKey, start: u32, limit: u32) -> ProposalPage {
+    let mut proposals = SdkVec::new(env);
+    for proposal_id in storage::list_page::<u64>(env, list, start, limit.min(MAX_PAGE_SIZE)).iter() {
+        if let Ok(proposal) = get_proposal(env, proposal_id) {
+            proposals.push_back(proposal);
         }
     }
-    false
+    ProposalPage { proposals, total: storage::list_len(env, list) }
 }
@@ -0,0 +1,173 @@
+use soroban_sdk::{Address, Env, Vec as SdkVec};
+use crate::constant::{VOTE_DELEGATED, DELEGATION_REVOKED, MAX_DELEGATION_DEPTH};
+use crate::state::{
+    DataKey, DelegationKey, DelegationScope, Proposal, ProposalType, ProxyVote, VoteDelegation, VoteKey, VoteRecord,
+};
+use crate::storage;
+use crate::instructions::dao_governance::{DAOGovernanceError, PROPOSAL_TYPES, eligible_weight};
+use crate::instructions::user_management::{get_council_members, is_council_member};
+
+pub fn delegate_vote(
+    env: &Env,
+    delegator: Address,
+    delegate: Address,
+    scope: DelegationScope,
+) -> Result<bool, DAOGovernanceError> {
+    delegator.require_auth();
+    if !is_council_member(env, &delegator) {
+        return Err(DAOGovernanceError::Unauthorized);
+    }
+    if delegator == delegate || !is_council_member(env, &delegate) {
+        return Err(DAOGovernanceError::InvalidDelegation);
+    }
+
+    // Following the delegate's own delegations must never lead back to the delegator
+    for proposal_type in PROPOSAL_TYPES.iter() {
+        let affected = match &scope {
+            DelegationScope::Only(only) => only == proposal_type,
+            // A type-specific delegation shadows the `All` one, so that type is unaffected
+            DelegationScope::All => get_delegation(env, delegator.clone(), DelegationScope::Only(proposal_type.clone())).is_none(),
+        };
+        if affected && chain_reaches(env, &delegate, &delegator, proposal_type) {
+            return Err(DAOGovernanceError::DelegationCycle);
+        }
+    }
+
+    let previous = get_delegation(env, delegator.clone(), scope.clone());
+    storage::save(env, &VoteDelegation {
+        delegator: delegator.clone(),
+        delegate: delegate.clone(),
+        scope: scope.clone(),
+        created_at: env.ledger().timestamp(),
+    });
+
+    let index_key = DataKey::Delegators(delegate.clone());
+    if !storage::load_index::<Address>(env, &index_key).contains(&delegator) {
+        storage::push_index(env, &index_key, delegator.clone());
+    }
+    if let Some(previous) = previous {
+        unlink(env, &delegator, &previous.delegate);
+    }
+
+    env.events().publish((VOTE_DELEGATED, delegator), (delegate, scope));
+    Ok(true)
+}
+
+pub fn revoke_delegation(env: &Env, delegator: Address, scope: DelegationScope) -> Result<bool, DAOGovernanceError> {
+    delegator.require_auth();
+    let delegation = get_delegation(env, delegator.clone(), scope.clone())
+        .ok_or(DAOGovernanceError::DelegationNotFound)?;
+
+    storage::remove(env, &DataKey::Delegation(DelegationKey { delegator: delegator.clone(), scope: scope.clone() }));
+    unlink(env, &delegator, &delegation.delegate);
+
+    env.events().publish((DELEGATION_REVOKED, delegator), (delegation.delegate, scope));
+    Ok(true)
+}
+
+pub fn get_delegation(env: &Env, delegator: Address, scope: DelegationScope) -> Option<VoteDelegation> {
+    storage::load(env, &DataKey::Delegation(DelegationKey { delegator, scope }))
+}
+
+// Who votes for `member` on proposals of this type, if anyone
+pub fn effective_delegate(env: &Env, member: &Address, proposal_type: &ProposalType) -> Option<Address> {
+    get_delegation(env, member.clone(), DelegationScope::Only(proposal_type.clone()))
+        .or_else(|| get_delegation(env, member.clone(), DelegationScope::All))
+        .map(|delegation| delegation.delegate)
+}
+
+// Members whose weight `voter` carries on this proposal: everyone whose delegation chain
+// for the proposal's type leads to the voter without passing through a member who voted
+// directly. Chains longer than MAX_DELEGATION_DEPTH hops are not followed.
+pub fn represented_members(env: &Env, proposal: &Proposal, voter: &Address) -> SdkVec<(Address, u32)> {
+    let mut represented = SdkVec::new(env);
+    let mut frontier = SdkVec::from_array(env, [voter.clone()]);
+
+    for _ in 0..MAX_DELEGATION_DEPTH {
+        let mut next = SdkVec::new(env);
+        for node in frontier.iter() {
+            for delegator in storage::load_index::<Address>(env, &DataKey::Delegators(node.clone())).iter() {
+                if effective_delegate(env, &delegator, &proposal.proposal_type) != Some(node.clone())
+                    || has_voted(env, proposal.id, &delegator)
+                {
+                    continue;
+                }
+                if let Some(weight) = eligible_weight(env, &delegator, proposal) {
+                    represented.push_back((delegator.clone(), weight));
+                    next.push_back(delegator);
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+    represented
+}
+
+// Takes back weight a delegate cast for `member`, removing it from the proposal's tallies
+// and from the delegate's vote record. The caller saves the proposal.
+pub fn release_proxy(env: &Env, proposal: &mut Proposal, member: &Address) -> Option<ProxyVote> {
+    let key = DataKey::ProxyVote(VoteKey { proposal_id: proposal.id, voter: member.clone() });
+    let proxy = storage::load::<ProxyVote>(env, &key)?;
+    storage::remove(env, &key);
+
+    if proxy.vote {
+        proposal.votes_for -= proxy.weight;
+        proposal.delegated_for -= proxy.weight;
+    } else {
+        proposal.votes_against -= proxy.weight;
+        proposal.delegated_against -= proxy.weight;
+    }
+
+    let vote_key = DataKey::Vote(VoteKey { proposal_id: proposal.id, voter: proxy.representative.clone() });
+    if let Some(mut record) = storage::load::<VoteRecord>(env, &vote_key) {
+        record.delegated_weight -= proxy.weight;
+        storage::save(env, &record);
+    }
+    Some(proxy)
+}
+
+pub fn is_proxied(env: &Env, proposal_id: u64, member: &Address) -> bool {
+    storage::has(env, &DataKey::ProxyVote(VoteKey { proposal_id, voter: member.clone() }))
+}
+
+fn has_voted(env: &Env, proposal_id: u64, member: &Address) -> bool {
+    storage::has(env, &DataKey::Vote(VoteKey { proposal_id, voter: member.clone() }))
+}
+
+fn chain_reaches(env: &Env, start: &Address, target: &Address, proposal_type: &ProposalType) -> bool {
+    let mut current = start.clone();
+    // Existing chains are acyclic, so they end within one hop per council member
+    for _ in 0..=get_council_members(env).len() {
+        if current == *target {
+            return true;
+        }
+        match effective_delegate(env, &current, proposal_type) {
+            Some(next) => current = next,
+            None => return false,
+        }
+    }
+    true
+}
+
+// Drops the delegator from the delegate's index once no scope points there any more
+fn unlink(env: &Env, delegator: &Address, delegate: &Address) {
+    let still_delegated = get_delegation(env, delegator.clone(), DelegationScope::All)
+        .into_iter()
+        .chain(PROPOSAL_TYPES.iter().filter_map(|proposal_type| {
+            get_delegation(env, delegator.clone(), DelegationScope::Only(proposal_type.clone()))
+        }))
+        .any(|delegation| delegation.delegate == *delegate);
+    if still_delegated {
+        return;
+    }
+
+    let index_key = DataKey::Delegators(delegate.clone());
+    let mut delegators = storage::load_index::<Address>(env, &index_key);
+    if let Some(position) = delegators.first_index_of(delegator) {
+        delegators.remove(position);
+        storage::save_index(env, &index_key, &delegators);
+    }
+}
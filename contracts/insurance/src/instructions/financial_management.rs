@@ -1,11 +1,13 @@
-use soroban_sdk::{Address, Env, IntoVal, Map, String, Symbol, Vec};
-use crate::state::{SafetyPool, Payment, PlatformConfig};
-use crate::instructions::user_management::is_council_member;
+use soroban_sdk::{token, Address, Env, IntoVal, Map, Symbol, Vec};
+use crate::state::{SafetyPool, Payment, PlatformConfig, Claim, DataKey, FinancialAction, LegacyClaim, ListKey};
+use crate::storage;
+use crate::instructions::user_management::{get_council_members, is_council_member};
+use crate::instructions::staking;
 use crate::constant::{
-    SAFETY_POOL, PREMIUM_PAYMENTS, CLAIMS, PLATFORM_CONFIG, EMERGENCY_FUND_FREEZE,
-    EXTERNAL_FUNDING_ADDED, RESERVE_FUNDS_WITHDRAWN, INVESTMENT_RETURNS_UPDATED,
+    SAFETY_POOL, PREMIUM_PAYMENTS, PREMIUM_TOKEN, CLAIMS, PLATFORM_CONFIG, EMERGENCY_FUND_FREEZE,
+    EXTERNAL_FUNDING_ADDED, RESERVE_FUNDS_WITHDRAWN,
     MINIMUM_RESERVE_UPDATED, RESERVE_RATIO_UPDATED, FINANCIAL_AUDIT_COMPLETED,
-    AUDIT_DISCREPANCY_FOUND, PLATFORM_CONFIG_UPDATED, EMERGENCY_FUND_UNFREEZE
+    AUDIT_DISCREPANCY_FOUND, MAX_PAGE_SIZE, PLATFORM_CONFIG_UPDATED, EMERGENCY_FUND_UNFREEZE, PREMIUM_TOKEN_UPDATED
 };
 
 #[derive(Debug, Clone)]
@@ -15,8 +17,8 @@ pub enum FinancialManagementError {
     InsufficientReserves,
     InvalidRatio,
     FundFrozen,
-    StorageError,
-    ValidationError,
+    TokenInUse,
+    InvalidConfig,
 }
 
 pub type FinancialResult<T> = Result<T, FinancialManagementError>;
@@ -37,6 +39,7 @@ impl FinancialManagementService {
         funder: Address, 
         amount: i128
     ) -> FinancialResult<bool> {
+        funder.require_auth();
                 Self::ensure_council_member(env, &funder)?;
         
                 Self::validate_positive_amount(amount)?;
@@ -53,85 +56,187 @@ impl FinancialManagementService {
         Ok(true)
     }
 
-    pub fn withdraw_reserve_funds(
-        env: &Env, 
-        withdrawer: Address, 
-        amount: i128, 
-        purpose: String
+    // Applies a treasury action.
+    pub fn execute_financial_action(
+        env: &Env,
+        executor: Address,
+        action: FinancialAction
     ) -> FinancialResult<bool> {
-                Self::ensure_council_member(env, &withdrawer)?;
-        
-                Self::ensure_positive_amount(amount)?;
-        
+        match action {
+            FinancialAction::WithdrawReserve(withdrawal) => {
+                Self::ensure_positive_amount(withdrawal.amount)?;
                 Self::ensure_funds_not_frozen(env)?;
 
-        let mut safety_pool = Self::get_safety_pool(env);
-        
-                Self::validate_reserve_withdrawal(env, &safety_pool, amount)?;
+                let mut safety_pool = Self::get_safety_pool(env);
+                Self::validate_reserve_withdrawal(env, &safety_pool, withdrawal.amount)?;
+                // The ledger balance can exceed what is actually held, see calculate_claim_capacity
+                if withdrawal.amount > Self::unstaked_holdings(env) {
+                    return Err(FinancialManagementError::InsufficientReserves);
+                }
 
-        safety_pool.total_balance -= amount;
-        Self::save_safety_pool(env, &safety_pool);
+                safety_pool.total_balance -= withdrawal.amount;
+                Self::save_safety_pool(env, &safety_pool);
+                if let Some(token) = Self::get_premium_token(env) {
+                    token::Client::new(env, &token).transfer(
+                        &env.current_contract_address(),
+                        &withdrawal.recipient,
+                        &withdrawal.amount,
+                    );
+                }
+                Self::emit_event(
+                    env,
+                    RESERVE_FUNDS_WITHDRAWN,
+                    executor,
+                    (withdrawal.recipient, withdrawal.amount, withdrawal.purpose)
+                );
+            }
+            FinancialAction::SetMinimumReserve(new_minimum) => {
+                Self::ensure_positive_amount(new_minimum)?;
+
+                let mut safety_pool = Self::get_safety_pool(env);
+                safety_pool.minimum_reserve = new_minimum;
+                Self::save_safety_pool(env, &safety_pool);
+                Self::emit_event(env, MINIMUM_RESERVE_UPDATED, executor, new_minimum);
+            }
+            FinancialAction::SetReserveRatio(new_ratio) => {
+                Self::validate_percentage_ratio(new_ratio)?;
+
+                let mut safety_pool = Self::get_safety_pool(env);
+                safety_pool.reserve_ratio = new_ratio;
+                Self::save_safety_pool(env, &safety_pool);
+                Self::emit_event(env, RESERVE_RATIO_UPDATED, executor, new_ratio);
+            }
+            FinancialAction::SetPremiumToken(token) => {
+                // Swapping the token under held funds would strand them in the old one
+                if Self::get_safety_pool(env).total_balance != 0 || staking::total_staked(env) != 0 {
+                    return Err(FinancialManagementError::TokenInUse);
+                }
+                env.storage().instance().set(&PREMIUM_TOKEN, &token);
+                Self::emit_event(env, PREMIUM_TOKEN_UPDATED, executor, token);
+            }
+            FinancialAction::FreezeFunds(reason) => {
+                Self::set_fund_freeze_status(env, true);
+                Self::emit_event(env, EMERGENCY_FUND_FREEZE, executor, reason);
+            }
+            FinancialAction::UnfreezeFunds => {
+                Self::set_fund_freeze_status(env, false);
+                Self::emit_event(env, EMERGENCY_FUND_UNFREEZE, executor, ());
+            }
+        }
+        Ok(true)
+    }
 
-        Self::emit_event(env, RESERVE_FUNDS_WITHDRAWN, withdrawer, (amount, purpose));
+    // Replaces the platform config.
+    pub fn execute_config_update(env: &Env, executor: Address, config: PlatformConfig) -> FinancialResult<bool> {
+        // The sitting council has to fit within the new size
+        if !Self::is_valid_config(&config) || get_council_members(env).len() as u64 > config.council_size {
+            return Err(FinancialManagementError::InvalidConfig);
+        }
+        Self::save_platform_config(env, &config);
+        Self::emit_event(env, PLATFORM_CONFIG_UPDATED, executor, ());
         Ok(true)
     }
 
-    pub fn update_investment_returns(
-        env: &Env, 
-        updater: Address, 
-        returns: i128
-    ) -> FinancialResult<bool> {
-                Self::ensure_council_member(env, &updater)?;
-        
-                Self::ensure_funds_not_frozen(env)?;
+    // Checks shared by deployment and config updates. A base quorum larger than the council
+    // could never be met.
+    pub fn is_valid_config(config: &PlatformConfig) -> bool {
+        config.council_size > 0
+            && config.minimum_quorum > 0
+            && config.minimum_quorum as u64 <= config.council_size
+            && config.proposal_duration_days > 0
+            && config.max_claim_amount_ratio > 0
+            && config.max_claim_amount_ratio <= 10000
+            && config.penalty_rate <= 10000
+    }
+
+    // Seeds the token, platform config and reserve minimums during contract initialization.
+    // Authorization is handled by the caller.
+    pub fn bootstrap(
+        env: &Env,
+        token: Address,
+        config: PlatformConfig,
+        minimum_reserve: i128,
+        reserve_ratio: u64
+    ) -> FinancialResult<()> {
+        Self::ensure_positive_amount(minimum_reserve)?;
+        Self::validate_percentage_ratio(reserve_ratio)?;
 
         let mut safety_pool = Self::get_safety_pool(env);
-        safety_pool.total_balance += returns;
-        safety_pool.investment_returns += returns;
+        safety_pool.minimum_reserve = minimum_reserve;
+        safety_pool.reserve_ratio = reserve_ratio;
 
+        env.storage().instance().set(&PREMIUM_TOKEN, &token);
+        Self::save_platform_config(env, &config);
         Self::save_safety_pool(env, &safety_pool);
-        Self::emit_event(env, INVESTMENT_RETURNS_UPDATED, updater, returns);
-        
-        Ok(true)
+        Ok(())
     }
 
-    pub fn set_minimum_reserve(
-        env: &Env, 
-        setter: Address, 
-        new_minimum: i128
-    ) -> FinancialResult<bool> {
-                Self::ensure_council_member(env, &setter)?;
-        
-                Self::ensure_positive_amount(new_minimum)?;
+    pub fn get_premium_token(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&PREMIUM_TOKEN)
+    }
 
+    // Credits a collected premium to the safety pool and appends it to the payer's payment history.
+    pub fn record_premium_payment(env: &Env, payment: Payment) {
         let mut safety_pool = Self::get_safety_pool(env);
-        safety_pool.minimum_reserve = new_minimum;
-
+        safety_pool.total_balance += payment.amount;
+        safety_pool.premium_contributions += payment.amount;
         Self::save_safety_pool(env, &safety_pool);
-        Self::emit_event(env, MINIMUM_RESERVE_UPDATED, setter, new_minimum);
-        
-        Ok(true)
+
+        storage::list_push(env, &ListKey::Payments(payment.user.clone()), &payment);
     }
 
-    pub fn update_reserve_ratio(
-        env: &Env, 
-        updater: Address, 
-        new_ratio: u64
-    ) -> FinancialResult<bool> {
-                Self::ensure_council_member(env, &updater)?;
-        
-                Self::validate_percentage_ratio(new_ratio)?;
+    // Up to `limit` of a member's premium payments (never more than MAX_PAGE_SIZE), oldest first
+    pub fn get_premium_payments(env: &Env, payer: Address, start: u32, limit: u32) -> Vec<Payment> {
+        storage::list_page(env, &ListKey::Payments(payer), start, limit.min(MAX_PAGE_SIZE))
+    }
 
+    // Debits a paid-out claim from the safety pool; the claim record itself carries the payout.
+    pub fn record_claim_payout(env: &Env, claim: &Claim) {
         let mut safety_pool = Self::get_safety_pool(env);
-        safety_pool.reserve_ratio = new_ratio;
+        safety_pool.total_balance -= claim.amount;
+        safety_pool.claim_payouts += claim.amount;
+        Self::save_safety_pool(env, &safety_pool);
+    }
+
+    // Moves the payment and paid-claim ledgers that earlier versions kept as single maps in
+    // instance storage into per-record persistent entries. Returns how many entries moved;
+    // once the maps are gone it does nothing.
+    pub fn migrate_legacy_ledgers(env: &Env) -> u32 {
+        let mut migrated = 0;
+
+        let payments: Map<Address, Vec<Payment>> = env.storage().instance().get(&PREMIUM_PAYMENTS)
+            .unwrap_or_else(|| Map::new(env));
+        for (payer, history) in payments.iter() {
+            for payment in history.iter() {
+                storage::list_push(env, &ListKey::Payments(payer.clone()), &payment);
+                migrated += 1;
+            }
+        }
+        env.storage().instance().remove(&PREMIUM_PAYMENTS);
+
+        // Paid claims were copies; the claim's own record wins when both exist
+        let claims: Map<u64, LegacyClaim> = env.storage().instance().get(&CLAIMS)
+            .unwrap_or_else(|| Map::new(env));
+        for (claim_id, claim) in claims.iter() {
+            if !storage::has(env, &DataKey::Claim(claim_id)) {
+                storage::save(env, &Claim::from(claim));
+                migrated += 1;
+            }
+        }
+        env.storage().instance().remove(&CLAIMS);
 
+        migrated
+    }
+
+    // Credits slashed stake to the safety pool; the tokens are already held by the contract.
+    pub fn record_slashed_stake(env: &Env, amount: i128) {
+        let mut safety_pool = Self::get_safety_pool(env);
+        safety_pool.total_balance += amount;
         Self::save_safety_pool(env, &safety_pool);
-        Self::emit_event(env, RESERVE_RATIO_UPDATED, updater, new_ratio);
-        
-        Ok(true)
     }
 
         pub fn conduct_financial_audit(env: &Env, auditor: Address) -> FinancialResult<bool> {
+        auditor.require_auth();
                 Self::ensure_council_member(env, &auditor)?;
 
         let mut safety_pool = Self::get_safety_pool(env);
@@ -176,100 +281,23 @@ impl FinancialManagementService {
         (is_healthy, safety_pool.total_balance, minimum_required)
     }
 
+    // What a payout may take: the pool above its minimum reserve, but never more than the
+    // contract really holds once stakers' tokens are set aside. External funding and
+    // investment returns raise the pool's ledger balance without moving any tokens.
     pub fn calculate_claim_capacity(env: &Env) -> i128 {
         let safety_pool = Self::get_safety_pool(env);
         let available_for_claims = safety_pool.total_balance - safety_pool.minimum_reserve;
-        
-        available_for_claims.max(0)
-    }
 
-    pub fn get_premium_payment_summary(env: &Env) -> (i128, u64, i128) {
-        let payments = Self::get_premium_payments(env);
-        let mut total_collected = 0i128;
-        let mut payment_count = 0u64;
-
-        for (_, payment_list) in payments.iter() {
-            for payment in payment_list.iter() {
-                total_collected += payment.amount;
-                payment_count += 1;
-            }
-        }
-
-        let average_payment = if payment_count > 0 {
-            total_collected / payment_count as i128
-        } else {
-            0
-        };
-
-        (total_collected, payment_count, average_payment)
-    }
-
-    pub fn get_recent_financial_activity(
-        env: &Env, 
-        days_back: u64
-    ) -> (i128, i128, u64, u64) {
-        let cutoff_time = env.ledger().timestamp() - (days_back * 24 * 60 * 60);
-        let mut recent_premiums = 0i128;
-        let mut recent_claims = 0i128;
-        let mut premium_count = 0u64;
-        let mut claim_count = 0u64;
-
-                let payments = Self::get_premium_payments(env);
-        for (_, payment_list) in payments.iter() {
-            for payment in payment_list.iter() {
-                if payment.payment_date >= cutoff_time {
-                    recent_premiums += payment.amount;
-                    premium_count += 1;
-                }
-            }
-        }
-
-                let claims = Self::get_claims(env);
-        for (_, claim) in claims.iter() {
-            if let Some(payout_date) = claim.payout_date {
-                if payout_date >= cutoff_time {
-                    recent_claims += claim.amount;
-                    claim_count += 1;
-                }
-            }
-        }
-
-        (recent_premiums, recent_claims, premium_count, claim_count)
-    }
-
-        pub fn set_platform_config(
-        env: &Env, 
-        setter: Address, 
-        config: PlatformConfig
-    ) -> FinancialResult<bool> {
-                Self::ensure_council_member(env, &setter)?;
-
-        Self::save_platform_config(env, &config);
-        Self::emit_event(env, PLATFORM_CONFIG_UPDATED, setter, ());
-        
-        Ok(true)
+        available_for_claims.min(Self::unstaked_holdings(env)).max(0)
     }
 
-        pub fn emergency_fund_freeze(
-        env: &Env, 
-        freezer: Address, 
-        reason: String
-    ) -> FinancialResult<bool> {
-                Self::ensure_council_member(env, &freezer)?;
-
-        Self::set_fund_freeze_status(env, true);
-        Self::emit_event(env, EMERGENCY_FUND_FREEZE, freezer, reason);
-        
-        Ok(true)
-    }
-
-    pub fn emergency_fund_unfreeze(env: &Env, unfreezer: Address) -> FinancialResult<bool> {
-                Self::ensure_council_member(env, &unfreezer)?;
-
-        Self::set_fund_freeze_status(env, false);
-        Self::emit_event(env, EMERGENCY_FUND_UNFREEZE, unfreezer, ());
-        
-        Ok(true)
+    // Premium tokens held by the contract that do not belong to stakers
+    fn unstaked_holdings(env: &Env) -> i128 {
+        let held = match Self::get_premium_token(env) {
+            Some(token) => token::Client::new(env, &token).balance(&env.current_contract_address()),
+            None => 0,
+        };
+        held - staking::total_staked(env)
     }
 
     pub fn is_fund_frozen(env: &Env) -> bool {
@@ -291,18 +319,8 @@ impl FinancialManagementService {
         env.storage().instance().set(&SAFETY_POOL, safety_pool);
     }
 
-    fn get_premium_payments(env: &Env) -> Map<Address, Vec<Payment>> {
-        env.storage().instance().get(&PREMIUM_PAYMENTS)
-            .unwrap_or_else(|| Map::new(env))
-    }
-
-    fn get_claims(env: &Env) -> Map<u64, crate::state::Claim> {
-        env.storage().instance().get(&CLAIMS)
-            .unwrap_or_else(|| Map::new(env))
-    }
-
-    fn get_platform_config(env: &Env) -> PlatformConfig {
-        env.storage().instance().get(&PLATFORM_CONFIG).unwrap_or_else(|| PlatformConfig {
+    pub fn get_platform_config(env: &Env) -> PlatformConfig {
+        env.storage().instance().get(&PLATFORM_CONFIG).unwrap_or(PlatformConfig {
             grace_period_weeks: 2,
             minimum_quorum: 3,
             proposal_duration_days: 7,
@@ -382,24 +400,11 @@ impl FinancialManagementService {
         env.events().publish((event_symbol, address), data);
     }
 
+        // Running totals kept on the pool, so the summary never walks the payment history
         fn calculate_financial_summary(env: &Env) -> (i128, i128, i128) {
-        let payments = Self::get_premium_payments(env);
-        let mut total_premiums = 0i128;
-        
-        for (_, payment_list) in payments.iter() {
-            for payment in payment_list.iter() {
-                total_premiums += payment.amount;
-            }
-        }
-
-        let claims = Self::get_claims(env);
-        let mut total_claims = 0i128;
-        
-        for (_, claim) in claims.iter() {
-            if claim.status == crate::state::ClaimStatus::Paid {
-                total_claims += claim.amount;
-            }
-        }
+        let safety_pool = Self::get_safety_pool(env);
+        let total_premiums = safety_pool.premium_contributions;
+        let total_claims = safety_pool.claim_payouts;
 
         let net_balance = total_premiums - total_claims;
         (total_premiums, total_claims, net_balance)
@@ -410,32 +415,29 @@ pub fn get_safety_pool_balance(env: &Env) -> i128 {
     FinancialManagementService::get_safety_pool_balance(env)
 }
 
+#[allow(dead_code)]
 pub fn get_safety_pool_details(env: &Env) -> SafetyPool {
     FinancialManagementService::get_safety_pool_details(env)
 }
 
-pub fn add_external_funding(env: &Env, funder: Address, amount: i128) -> bool {
-    FinancialManagementService::add_external_funding(env, funder, amount).unwrap_or(false)
-}
-
-pub fn withdraw_reserve_funds(env: &Env, withdrawer: Address, amount: i128, purpose: String) -> bool {
-    FinancialManagementService::withdraw_reserve_funds(env, withdrawer, amount, purpose).unwrap_or(false)
+pub fn add_external_funding(env: &Env, funder: Address, amount: i128) -> FinancialResult<bool> {
+    FinancialManagementService::add_external_funding(env, funder, amount)
 }
 
-pub fn update_investment_returns(env: &Env, updater: Address, returns: i128) -> bool {
-    FinancialManagementService::update_investment_returns(env, updater, returns).unwrap_or(false)
+pub fn get_premium_token(env: &Env) -> Option<Address> {
+    FinancialManagementService::get_premium_token(env)
 }
 
-pub fn set_minimum_reserve(env: &Env, setter: Address, new_minimum: i128) -> bool {
-    FinancialManagementService::set_minimum_reserve(env, setter, new_minimum).unwrap_or(false)
+pub fn get_premium_payments(env: &Env, payer: Address, start: u32, limit: u32) -> Vec<Payment> {
+    FinancialManagementService::get_premium_payments(env, payer, start, limit)
 }
 
-pub fn update_reserve_ratio(env: &Env, updater: Address, new_ratio: u64) -> bool {
-    FinancialManagementService::update_reserve_ratio(env, updater, new_ratio).unwrap_or(false)
+pub fn migrate_legacy_ledgers(env: &Env) -> u32 {
+    FinancialManagementService::migrate_legacy_ledgers(env)
 }
 
-pub fn conduct_financial_audit(env: &Env, auditor: Address) -> bool {
-    FinancialManagementService::conduct_financial_audit(env, auditor).unwrap_or(false)
+pub fn conduct_financial_audit(env: &Env, auditor: Address) -> FinancialResult<bool> {
+    FinancialManagementService::conduct_financial_audit(env, auditor)
 }
 
 pub fn get_financial_summary(env: &Env) -> (i128, i128, i128, i128) {
@@ -446,30 +448,6 @@ pub fn check_reserve_health(env: &Env) -> (bool, i128, i128) {
     FinancialManagementService::check_reserve_health(env)
 }
 
-pub fn calculate_claim_capacity(env: &Env) -> i128 {
-    FinancialManagementService::calculate_claim_capacity(env)
-}
-
-pub fn get_premium_payment_summary(env: &Env) -> (i128, u64, i128) {
-    FinancialManagementService::get_premium_payment_summary(env)
-}
-
-pub fn get_recent_financial_activity(env: &Env, days_back: u64) -> (i128, i128, u64, u64) {
-    FinancialManagementService::get_recent_financial_activity(env, days_back)
-}
-
-pub fn set_platform_config(env: &Env, setter: Address, config: PlatformConfig) -> bool {
-    FinancialManagementService::set_platform_config(env, setter, config).unwrap_or(false)
-}
-
-pub fn emergency_fund_freeze(env: &Env, freezer: Address, reason: String) -> bool {
-    FinancialManagementService::emergency_fund_freeze(env, freezer, reason).unwrap_or(false)
-}
-
-pub fn emergency_fund_unfreeze(env: &Env, unfreezer: Address) -> bool {
-    FinancialManagementService::emergency_fund_unfreeze(env, unfreezer).unwrap_or(false)
-}
-
 pub fn is_fund_frozen(env: &Env) -> bool {
     FinancialManagementService::is_fund_frozen(env)
 }
@@ -1,6 +1,13 @@
 pub mod user_management;
 pub mod dao_governance;
+pub mod delegation;
+pub mod secret_ballot;
+pub mod claim_assessment;
+pub mod tally;
+pub mod staking;
 pub mod policy_management;
 pub mod subscription_management;
 pub mod claims_processing;
 pub mod financial_management;
+pub mod admin;
+pub mod proposal_execution;
@@ -1,18 +1,15 @@
-use soroban_sdk::{Env, Address, String, Vec};
+use soroban_sdk::{Env, Address, String};
 use crate::constant::{EVENT_POL_CRT, EVENT_POL_ARC, EVENT_POL_DEL};
 use crate::state::{DataKey, Policy, PolicyStatus, PolicyParams};
-use crate::instructions::user_management::is_user_approved;
+use crate::instructions::user_management::{is_council_member, is_user_approved};
+use crate::storage::{self, next_id};
 
 #[derive(Debug)]
 pub enum PolicyManagementError {
     PolicyNotFound,
-    PolicyAlreadyExists,
     Unauthorized,
     InvalidPolicyData,
-    InsufficientCoverage,
     PolicyNotActive,
-    CoverageExceeded,
-    InvalidPolicyParams,
 }
 
 pub fn create_policy(
@@ -22,17 +19,28 @@ pub fn create_policy(
     description: String,
     params: PolicyParams,
 ) -> Result<u64, PolicyManagementError> {
+    creator.require_auth();
     
     if !is_user_approved(env, &creator) {
         return Err(PolicyManagementError::Unauthorized);
     }
 
-    
+    execute_policy_creation(env, creator, title, description, params)
+}
+
+// Creates a policy from an approved draft.
+pub fn execute_policy_creation(
+    env: &Env,
+    creator: Address,
+    title: String,
+    description: String,
+    params: PolicyParams,
+) -> Result<u64, PolicyManagementError> {
     if params.max_claim_amount <= 0 {
         return Err(PolicyManagementError::InvalidPolicyData);
     }
 
-    let policy_id = env.ledger().sequence() as u64;
+    let policy_id = next_id(env, DataKey::LatestPolicyId);
     let policy = Policy {
         id: policy_id,
         title: title.clone(),
@@ -43,7 +51,7 @@ pub fn create_policy(
         creator: creator.clone(),
     };
 
-    env.storage().instance().set(&DataKey::Policy(policy_id), &policy);
+    storage::save(env, &policy);
     
     env.events().publish(
         (EVENT_POL_CRT, policy_id),
@@ -61,12 +69,13 @@ pub fn update_policy(
     new_description: Option<String>,
     new_params: Option<PolicyParams>,
 ) -> Result<bool, PolicyManagementError> {
+    updater.require_auth();
     let policy_key = DataKey::Policy(policy_id);
-    let mut policy = env.storage().instance().get::<_, Policy>(&policy_key)
+    let mut policy = storage::load::<Policy>(env, &policy_key)
         .ok_or(PolicyManagementError::PolicyNotFound)?;
 
     
-    if policy.creator != updater && !is_user_approved(env, &updater) {
+    if policy.creator != updater && !is_council_member(env, &updater) {
         return Err(PolicyManagementError::Unauthorized);
     }
 
@@ -90,7 +99,7 @@ pub fn update_policy(
         }
     }
 
-    env.storage().instance().set(&policy_key, &policy);
+    storage::save(env, &policy);
 
     env.events().publish(
         (EVENT_POL_ARC, policy_id),
@@ -105,22 +114,31 @@ pub fn archive_policy(
     policy_id: u64,
     archiver: Address,
 ) -> Result<bool, PolicyManagementError> {
-    let policy_key = DataKey::Policy(policy_id);
-    let mut policy = env.storage().instance().get::<_, Policy>(&policy_key)
-        .ok_or(PolicyManagementError::PolicyNotFound)?;
+    archiver.require_auth();
+    let policy = get_policy(env, policy_id)?;
 
     
-    if policy.creator != archiver && !is_user_approved(env, &archiver) {
+    if policy.creator != archiver && !is_council_member(env, &archiver) {
         return Err(PolicyManagementError::Unauthorized);
     }
 
-    
+    execute_policy_archival(env, policy_id, archiver)
+}
+
+// Archives a policy.
+pub fn execute_policy_archival(
+    env: &Env,
+    policy_id: u64,
+    archiver: Address,
+) -> Result<bool, PolicyManagementError> {
+    let mut policy = get_policy(env, policy_id)?;
+
     if policy.status != PolicyStatus::Active {
         return Err(PolicyManagementError::PolicyNotActive);
     }
 
     policy.status = PolicyStatus::Archived;
-    env.storage().instance().set(&policy_key, &policy);
+    storage::save(env, &policy);
 
     env.events().publish(
         (EVENT_POL_ARC, policy_id),
@@ -135,22 +153,32 @@ pub fn delete_policy(
     policy_id: u64,
     deleter: Address,
 ) -> Result<bool, PolicyManagementError> {
-    let policy_key = DataKey::Policy(policy_id);
-    let policy = env.storage().instance().get::<_, Policy>(&policy_key)
-        .ok_or(PolicyManagementError::PolicyNotFound)?;
+    deleter.require_auth();
+    let policy = get_policy(env, policy_id)?;
 
     
-    if policy.creator != deleter && !is_user_approved(env, &deleter) {
+    if policy.creator != deleter && !is_council_member(env, &deleter) {
         return Err(PolicyManagementError::Unauthorized);
     }
 
-    
+    execute_policy_deletion(env, policy_id, deleter)
+}
+
+// Deletes an archived policy.
+pub fn execute_policy_deletion(
+    env: &Env,
+    policy_id: u64,
+    deleter: Address,
+) -> Result<bool, PolicyManagementError> {
+    let policy_key = DataKey::Policy(policy_id);
+    let policy = get_policy(env, policy_id)?;
+
     if policy.status != PolicyStatus::Archived {
         return Err(PolicyManagementError::PolicyNotActive);
     }
 
     
-    env.storage().instance().remove(&policy_key);
+    storage::remove(env, &policy_key);
 
     env.events().publish(
         (EVENT_POL_DEL, policy_id),
@@ -161,36 +189,10 @@ pub fn delete_policy(
 }
 
 pub fn get_policy(env: &Env, policy_id: u64) -> Result<Policy, PolicyManagementError> {
-    env.storage().instance().get(&DataKey::Policy(policy_id))
+    storage::load(env, &DataKey::Policy(policy_id))
         .ok_or(PolicyManagementError::PolicyNotFound)
 }
 
-pub fn get_user_policies(env: &Env, user: Address) -> Result<Vec<u64>, PolicyManagementError> {
-    // This is a simplified implementation - in a real contract you might want to maintain an index
-    // For now, we'll return an empty vector as this would require more complex storage patterns
-    //TODO: Implement this
-    Ok(Vec::new(&env))
-}
-
-pub fn get_active_policies(env: &Env) -> Result<Vec<u64>, PolicyManagementError> {
-    // This is a simplified implementation - in a real contract you might want to maintain an index
-    // For now, we'll return an empty vector as this would require more complex storage patterns
-    //TODO: Implement this
-    Ok(Vec::new(&env))
-}
-
-pub fn calculate_premium(
-    env: &Env,
-    coverage_amount: i128,
-    risk_score: u32,
-    duration_days: u32,
-) -> i128 {
-    let base_rate = 50;     let risk_multiplier = 100 + (risk_score as i128 * 2); 
-    let duration_multiplier = (duration_days as i128 * 100) / 365; 
-
-    (coverage_amount * base_rate * risk_multiplier * duration_multiplier) / (100 * 100 * 100)
-}
-
 pub fn validate_policy_eligibility(
     env: &Env,
     user: Address,
@@ -202,7 +204,7 @@ pub fn validate_policy_eligibility(
     }
 
     
-    let policy = env.storage().instance().get::<_, Policy>(&DataKey::Policy(policy_id))
+    let policy = storage::load::<Policy>(env, &DataKey::Policy(policy_id))
         .ok_or(PolicyManagementError::PolicyNotFound)?;
 
     if policy.status != PolicyStatus::Active {
@@ -216,11 +218,7 @@ pub fn validate_policy_eligibility(
 }
 
 
-pub fn is_policy_active(env: &Env, policy: &Policy) -> bool {
+pub fn is_policy_active(_env: &Env, policy: &Policy) -> bool {
     policy.status == PolicyStatus::Active
 }
 
-
-pub fn can_use_policy_for_subscription(env: &Env, policy: &Policy) -> bool {
-    policy.status == PolicyStatus::Active
-}
@@ -0,0 +1,283 @@
+use soroban_sdk::xdr::FromXdr;
+use soroban_sdk::{vec, Address, Bytes, Env, String};
+use crate::constant::{PROPOSAL_EXECUTED, PROPOSAL_INVALIDATED, TIMELOCK_EXECUTION_WINDOW, MAX_BATCH_ACTIONS};
+use crate::error::Error;
+use crate::state::{
+    Claim, ClaimStatus, DataKey, Policy, PolicyStatus, Proposal, ProposalAction, ProposalStatus, ProposalType,
+    User, UserStatus,
+};
+use crate::storage;
+use crate::instructions::dao_governance::{self, DAOGovernanceError};
+use crate::instructions::user_management::{self, is_council_member};
+use crate::instructions::policy_management;
+use crate::instructions::claims_processing;
+use crate::instructions::staking;
+use crate::instructions::financial_management::FinancialManagementService;
+
+// Each module exposes the changes the DAO controls as `execute_*` functions. They do no caller
+// checks of their own: the passed vote is the authorization, so they are meant to be called from
+// here (or by entrypoints that have already authorized the caller).
+
+// Applies a passed proposal's payload through the owning module. Returns the
+// contract-level `Error` so failures from the target module surface unchanged.
+// A payload whose target has changed since the vote (e.g. the user is already banned)
+// marks the proposal `Invalid` with the reason and returns `false` instead.
+// Queued proposals run only between their ETA and the end of the execution window;
+// one that missed the window is marked `Expired` the same way.
+// A batch is applied in order, each action checked just before it runs so it sees the
+// effects of the ones before it. If any action fails, or turns out stale once earlier ones
+// have run, the whole execution reverts; only a stale first action marks the proposal `Invalid`.
+pub fn execute_proposal(env: &Env, proposal_id: u64, executor: Address) -> Result<bool, Error> {
+    executor.require_auth();
+    let mut proposal = storage::load::<Proposal>(env, &DataKey::DAOProposal(proposal_id))
+        .ok_or(DAOGovernanceError::ProposalNotFound)?;
+
+    if proposal.status == ProposalStatus::Executed {
+        return Err(Error::ProposalAlreadyExecuted);
+    }
+    if !is_council_member(env, &executor) {
+        return Err(Error::Unauthorized);
+    }
+
+    if proposal.status == ProposalStatus::Queued {
+        let eta = proposal.eta.unwrap_or_default();
+        if env.ledger().timestamp() < eta {
+            return Err(DAOGovernanceError::TimelockActive.into());
+        }
+        if env.ledger().timestamp() > eta + TIMELOCK_EXECUTION_WINDOW {
+            dao_governance::set_status(env, &mut proposal, ProposalStatus::Expired);
+            proposal.status_reason = Some(String::from_str(env, "execution window passed"));
+            storage::save(env, &proposal);
+            env.events().publish((PROPOSAL_INVALIDATED, proposal_id), (executor, "execution window passed"));
+            return Ok(false);
+        }
+    } else if proposal.status != ProposalStatus::Passed {
+        return Err(Error::ProposalNotOpen);
+    }
+
+    let actions = match decode_action(env, &proposal.proposal_type, &proposal.execution_data)? {
+        ProposalAction::Batch(actions) => actions,
+        action => vec![env, action],
+    };
+    for (index, action) in actions.iter().enumerate() {
+        if let Some(reason) = invalid_reason(env, &action) {
+            // Earlier actions have already run and cannot be kept without the rest
+            if index > 0 {
+                return Err(DAOGovernanceError::InvalidProposalPayload.into());
+            }
+            dao_governance::set_status(env, &mut proposal, ProposalStatus::Invalid);
+            proposal.status_reason = Some(String::from_str(env, reason));
+            storage::save(env, &proposal);
+            env.events().publish((PROPOSAL_INVALIDATED, proposal_id), (executor, reason));
+            return Ok(false);
+        }
+        dispatch(env, &proposal, &executor, action)?;
+    }
+
+    dao_governance::set_status(env, &mut proposal, ProposalStatus::Executed);
+    proposal.executed_by = Some(executor.clone());
+    proposal.executed_at = Some(env.ledger().timestamp());
+    storage::save(env, &proposal);
+
+    env.events().publish(
+        (PROPOSAL_EXECUTED, proposal_id),
+        (executor, proposal.proposal_type)
+    );
+
+    Ok(true)
+}
+
+// Every `ProposalAction` encodes as a present `ScVal::Vec`: tag 16, then the option flag
+const ACTION_XDR_HEADER: [u8; 8] = [0, 0, 0, 16, 0, 0, 0, 1];
+
+// Decodes `execution_data` and checks the action belongs to the proposal's type.
+// A batch holds up to MAX_BATCH_ACTIONS actions, none of them batches; each must belong to
+// the proposal's type, except that a `GovernanceUpdate` batch may also carry policy and
+// treasury actions.
+// Runs when a proposal is created, so a bad payload never reaches a vote. Bytes that are
+// not valid XDR at all trap in the host, so anything not framed as a vector is turned away first.
+pub fn decode_action(
+    env: &Env,
+    proposal_type: &ProposalType,
+    execution_data: &Bytes,
+) -> Result<ProposalAction, DAOGovernanceError> {
+    if execution_data.len() < 8 || execution_data.slice(..8) != Bytes::from_array(env, &ACTION_XDR_HEADER) {
+        return Err(DAOGovernanceError::InvalidProposalPayload);
+    }
+    let action = ProposalAction::from_xdr(env, execution_data)
+        .map_err(|_| DAOGovernanceError::InvalidProposalPayload)?;
+
+    let matches = match &action {
+        ProposalAction::Batch(actions) => {
+            if actions.len() > MAX_BATCH_ACTIONS {
+                return Err(DAOGovernanceError::InvalidProposalPayload);
+            }
+            !actions.is_empty() && actions.iter().all(|action| batchable(proposal_type, &action))
+        }
+        action => belongs_to(proposal_type, action),
+    };
+    if !matches {
+        return Err(DAOGovernanceError::InvalidProposalPayload);
+    }
+
+    Ok(action)
+}
+
+// Actions against members and claims stay under their own type's quorum, threshold and
+// reputation rules, so a governance batch cannot carry them.
+fn batchable(proposal_type: &ProposalType, action: &ProposalAction) -> bool {
+    let governance_mix = matches!(
+        action,
+        ProposalAction::CreatePolicy(_)
+            | ProposalAction::ArchivePolicy(_)
+            | ProposalAction::DeletePolicy(_)
+            | ProposalAction::Treasury(_)
+    );
+    match action {
+        ProposalAction::Batch(_) => false,
+        _ => belongs_to(proposal_type, action) || (*proposal_type == ProposalType::GovernanceUpdate && governance_mix),
+    }
+}
+
+fn belongs_to(proposal_type: &ProposalType, action: &ProposalAction) -> bool {
+    matches!(
+        (proposal_type, action),
+        (ProposalType::PolicyCreation, ProposalAction::CreatePolicy(_))
+            | (ProposalType::PolicyArchival, ProposalAction::ArchivePolicy(_))
+            | (ProposalType::PolicyDeletion, ProposalAction::DeletePolicy(_))
+            | (ProposalType::UserApproval, ProposalAction::ApproveUser(_))
+            | (ProposalType::UserBan, ProposalAction::BanUser(_))
+            | (ProposalType::UserBan, ProposalAction::SlashStake(_))
+            | (ProposalType::MembershipChange, ProposalAction::AddCouncilMember(_))
+            | (ProposalType::MembershipChange, ProposalAction::RemoveCouncilMember(_))
+            | (ProposalType::FinancialDecision, ProposalAction::Treasury(_))
+            | (ProposalType::EmergencyAction, ProposalAction::Treasury(_))
+            | (ProposalType::ClaimResolution, ProposalAction::ResolveClaim(_))
+            | (ProposalType::GovernanceUpdate, ProposalAction::UpdateConfig(_))
+            | (ProposalType::GovernanceUpdate, ProposalAction::UpdateProposalConfig(_))
+    )
+}
+
+// Why the action can no longer be applied, if the state it targets has moved on
+fn invalid_reason(env: &Env, action: &ProposalAction) -> Option<&'static str> {
+    match action {
+        ProposalAction::ArchivePolicy(policy_id) => {
+            match storage::load::<Policy>(env, &DataKey::Policy(*policy_id)) {
+                None => Some("policy not found"),
+                Some(policy) if policy.status != PolicyStatus::Active => Some("policy is not active"),
+                Some(_) => None,
+            }
+        }
+        ProposalAction::DeletePolicy(policy_id) => {
+            // Deleted policies are removed from storage
+            match storage::load::<Policy>(env, &DataKey::Policy(*policy_id)) {
+                None => Some("policy already deleted"),
+                Some(policy) if policy.status != PolicyStatus::Archived => Some("policy is not archived"),
+                Some(_) => None,
+            }
+        }
+        ProposalAction::ApproveUser(user) => match load_user(env, user) {
+            None => Some("user not found"),
+            Some(user) if user.status == UserStatus::Banned => Some("user is banned"),
+            Some(user) if user.status == UserStatus::Active => Some("user already active"),
+            Some(_) => None,
+        },
+        ProposalAction::BanUser(user) => match load_user(env, user) {
+            None => Some("user not found"),
+            Some(user) if user.status == UserStatus::Banned => Some("user already banned"),
+            Some(_) => None,
+        },
+        ProposalAction::AddCouncilMember(member) => match load_user(env, member) {
+            None => Some("user not found"),
+            Some(user) if user.status == UserStatus::Banned => Some("user is banned"),
+            Some(user) if user.is_dao_member => Some("already a council member"),
+            Some(_) => None,
+        },
+        ProposalAction::ResolveClaim(decision) => {
+            match storage::load::<Claim>(env, &DataKey::Claim(decision.claim_id)) {
+                None => Some("claim not found"),
+                Some(claim) if !claims_processing::is_claim_pending(&claim) => Some("claim already processed"),
+                Some(claim) if claim.proposal_id.is_some() => Some("claim is decided by its own vote"),
+                Some(_) => None,
+            }
+        }
+        ProposalAction::RemoveCouncilMember(member) => match load_user(env, member) {
+            None => Some("user not found"),
+            Some(user) if !user.is_dao_member => Some("not a council member"),
+            Some(_) => None,
+        },
+        ProposalAction::SlashStake(slash) => match load_user(env, &slash.member) {
+            None => Some("user not found"),
+            Some(_) if staking::slashable_stake(env, &slash.member) == 0 => Some("no stake to slash"),
+            Some(_) => None,
+        },
+        ProposalAction::CreatePolicy(_)
+        | ProposalAction::Treasury(_)
+        | ProposalAction::UpdateConfig(_)
+        | ProposalAction::UpdateProposalConfig(_)
+        | ProposalAction::Batch(_) => None,
+    }
+}
+
+fn load_user(env: &Env, address: &Address) -> Option<User> {
+    storage::load(env, &DataKey::User(address.clone()))
+}
+
+fn dispatch(env: &Env, proposal: &Proposal, executor: &Address, action: ProposalAction) -> Result<(), Error> {
+    match action {
+        ProposalAction::CreatePolicy(draft) => {
+            // The proposer is recorded as the policy's creator
+            policy_management::execute_policy_creation(
+                env,
+                proposal.proposer.clone(),
+                draft.title,
+                draft.description,
+                draft.params,
+            )?;
+        }
+        ProposalAction::ArchivePolicy(policy_id) => {
+            policy_management::execute_policy_archival(env, policy_id, executor.clone())?;
+        }
+        ProposalAction::DeletePolicy(policy_id) => {
+            policy_management::execute_policy_deletion(env, policy_id, executor.clone())?;
+        }
+        ProposalAction::ApproveUser(user) => {
+            user_management::execute_user_approval(env, user)?;
+        }
+        ProposalAction::BanUser(user) => {
+            user_management::execute_user_ban(env, user, proposal.description.clone())?;
+        }
+        ProposalAction::AddCouncilMember(member) => {
+            user_management::execute_membership_change(env, member)?;
+        }
+        ProposalAction::Treasury(financial_action) => {
+            FinancialManagementService::execute_financial_action(env, executor.clone(), financial_action)?;
+        }
+        ProposalAction::ResolveClaim(decision) => {
+            let status = if decision.approved { ClaimStatus::Approved } else { ClaimStatus::Rejected };
+            claims_processing::execute_claim_resolution(
+                env,
+                decision.claim_id,
+                executor.clone(),
+                status,
+                decision.notes,
+            )?;
+        }
+        ProposalAction::UpdateConfig(config) => {
+            FinancialManagementService::execute_config_update(env, executor.clone(), config)?;
+        }
+        ProposalAction::UpdateProposalConfig(config) => {
+            dao_governance::execute_proposal_config_update(env, config)?;
+        }
+        ProposalAction::RemoveCouncilMember(member) => {
+            // The proposal description is the reason for the removal
+            user_management::execute_membership_removal(env, member, proposal.description.clone())?;
+        }
+        ProposalAction::SlashStake(slash) => {
+            staking::execute_stake_slash(env, executor.clone(), slash)?;
+        }
+        // `decode_action` never lets a batch nest inside another
+        ProposalAction::Batch(_) => return Err(DAOGovernanceError::InvalidProposalPayload.into()),
+    }
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Address, Bytes, BytesN, Env, String};
+use crate::constant::{VOTE_COMMITTED, VOTE_REVEALED};
+use crate::state::{DataKey, ListKey, Proposal, ProposalStatus, VoteCommit, VoteKey, VoteRecord};
+use crate::storage;
+use crate::instructions::dao_governance::{DAOGovernanceError, ensure_can_vote, get_proposal};
+use crate::instructions::tally::counted_weight;
+use crate::instructions::user_management::get_vote_weight;
+
+// Secret ballots run in two phases. Until `voting_period_end` members only commit to a hash
+// of their vote; afterwards they reveal it until `reveal_end`, and tallies are counted once
+// reveals close. Commitments never revealed count as abstentions. Ledger entries and reveal
+// arguments are public, so this only keeps the contract from exposing votes early.
+
+pub fn commit_vote(
+    env: &Env,
+    proposal_id: u64,
+    voter: Address,
+    commitment: BytesN<32>,
+) -> Result<bool, DAOGovernanceError> {
+    voter.require_auth();
+    let mut proposal = get_proposal(env, proposal_id)?;
+    ensure_can_vote(env, &proposal, &voter)?;
+    if proposal.reveal_end.is_none() {
+        return Err(DAOGovernanceError::InvalidVoteType);
+    }
+
+    // Weight is fixed at commit time, like an open vote; delegations do not apply
+    let weight = get_vote_weight(env, voter.clone())
+        .map_err(|_| DAOGovernanceError::Unauthorized)?;
+    storage::save(env, &VoteCommit {
+        proposal_id,
+        voter: voter.clone(),
+        commitment,
+        weight,
+        committed_at: env.ledger().timestamp(),
+    });
+
+    proposal.voters.push_back(voter.clone());
+    storage::save(env, &proposal);
+
+    env.events().publish((VOTE_COMMITTED, proposal_id), voter);
+    Ok(true)
+}
+
+pub fn reveal_vote(
+    env: &Env,
+    proposal_id: u64,
+    voter: Address,
+    vote_direction: bool,
+    salt: BytesN<32>,
+    metadata: Option<String>,
+) -> Result<bool, DAOGovernanceError> {
+    voter.require_auth();
+    let proposal = get_proposal(env, proposal_id)?;
+    let reveal_end = proposal.reveal_end.ok_or(DAOGovernanceError::InvalidVoteType)?;
+    if proposal.status != ProposalStatus::Open {
+        return Err(DAOGovernanceError::ProposalNotOpen);
+    }
+
+    let now = env.ledger().timestamp();
+    if now <= proposal.voting_period_end {
+        return Err(DAOGovernanceError::VotingStillOpen);
+    }
+    if now > reveal_end {
+        return Err(DAOGovernanceError::VotingPeriodEnded);
+    }
+
+    let vote_key = VoteKey { proposal_id, voter: voter.clone() };
+    let commit = storage::load::<VoteCommit>(env, &DataKey::VoteCommit(vote_key.clone()))
+        .ok_or(DAOGovernanceError::VoteNotFound)?;
+    if storage::has(env, &DataKey::Vote(vote_key)) {
+        return Err(DAOGovernanceError::AlreadyVoted);
+    }
+    if ballot_commitment(env, proposal_id, &voter, vote_direction, &salt) != commit.commitment {
+        return Err(DAOGovernanceError::RevealMismatch);
+    }
+
+    storage::save(env, &VoteRecord {
+        proposal_id,
+        voter: voter.clone(),
+        weight: commit.weight,
+        vote: vote_direction,
+        timestamp: now,
+        metadata,
+        delegated_weight: 0,
+    });
+    storage::list_push(env, &ListKey::MemberVotes(voter.clone()), &proposal_id);
+
+    env.events().publish((VOTE_REVEALED, proposal_id), voter);
+    Ok(true)
+}
+
+// sha256 of the proposal id (8 bytes, big-endian), the voter's address as XDR, the vote byte
+// (1 = for, 0 = against) and the salt. Binding the proposal and voter keeps a commitment
+// copied from another ballot or member from ever revealing.
+pub fn ballot_commitment(
+    env: &Env,
+    proposal_id: u64,
+    voter: &Address,
+    vote_direction: bool,
+    salt: &BytesN<32>,
+) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(env, &proposal_id.to_be_bytes());
+    preimage.append(&voter.clone().to_xdr(env));
+    preimage.push_back(vote_direction as u8);
+    preimage.append(&Bytes::from(salt.clone()));
+    env.crypto().sha256(&preimage).to_bytes()
+}
+
+pub fn get_vote_commit(env: &Env, proposal_id: u64, voter: Address) -> Option<VoteCommit> {
+    storage::load(env, &DataKey::VoteCommit(VoteKey { proposal_id, voter }))
+}
+
+// A secret ballot whose reveal window has not closed yet
+pub fn is_sealed(env: &Env, proposal: &Proposal) -> bool {
+    proposal.reveal_end.is_some_and(|reveal_end| env.ledger().timestamp() <= reveal_end)
+}
+
+// Counts the revealed votes into the proposal's tallies. The caller saves the proposal.
+pub fn tally_revealed_votes(env: &Env, proposal: &mut Proposal) {
+    for voter in proposal.voters.iter() {
+        let key = DataKey::Vote(VoteKey { proposal_id: proposal.id, voter });
+        if let Some(vote) = storage::load::<VoteRecord>(env, &key) {
+            let weight = counted_weight(&vote.weight, &proposal.tally_mode);
+            if vote.vote {
+                proposal.votes_for += weight;
+                proposal.yes_votes += 1;
+            } else {
+                proposal.votes_against += weight;
+                proposal.no_votes += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use soroban_sdk::{token, Address, Env};
+use crate::constant::{STAKE_ADDED, UNSTAKE_REQUESTED, STAKE_WITHDRAWN, STAKE_SLASHED, STAKE_UNBONDING_PERIOD, TOTAL_STAKED};
+use crate::state::{DataKey, StakeSlash, UnbondingStake, User, UserStatus};
+use crate::storage;
+use crate::instructions::user_management::refresh_vote_weight;
+use crate::instructions::financial_management::FinancialManagementService;
+
+#[derive(Debug)]
+pub enum StakingError {
+    UserNotFound,
+    Unauthorized,
+    InvalidAmount,
+    InsufficientStake,
+    NothingToWithdraw,
+    StakeStillUnbonding,
+    PaymentFailed,
+    InsufficientFunds,
+}
+
+// Locks premium tokens in the contract; the stake counts towards the member's vote weight.
+pub fn stake(env: &Env, member: Address, amount: i128) -> Result<bool, StakingError> {
+    member.require_auth();
+    if amount <= 0 {
+        return Err(StakingError::InvalidAmount);
+    }
+
+    let mut user = load_user(env, &member)?;
+    if user.status == UserStatus::Banned {
+        return Err(StakingError::Unauthorized);
+    }
+
+    let token = premium_token(env)?;
+    if token.balance(&member) < amount {
+        return Err(StakingError::InsufficientFunds);
+    }
+    token.transfer(&member, &env.current_contract_address(), &amount);
+    adjust_total_staked(env, amount);
+
+    user.staked_amount += amount;
+    storage::save(env, &user);
+    refresh_vote_weight(env, &user);
+
+    env.events().publish((STAKE_ADDED, member), (amount, user.staked_amount));
+    Ok(true)
+}
+
+// Moves stake into unbonding. It stops counting towards vote weight at once and can be
+// withdrawn after STAKE_UNBONDING_PERIOD; a second request adds to the pending amount and
+// restarts the period. Returns when the pending amount becomes withdrawable.
+pub fn request_unstake(env: &Env, member: Address, amount: i128) -> Result<u64, StakingError> {
+    member.require_auth();
+    if amount <= 0 {
+        return Err(StakingError::InvalidAmount);
+    }
+
+    let mut user = load_user(env, &member)?;
+    if amount > user.staked_amount {
+        return Err(StakingError::InsufficientStake);
+    }
+    user.staked_amount -= amount;
+    storage::save(env, &user);
+    refresh_vote_weight(env, &user);
+
+    let mut unbonding = get_unbonding(env, member.clone()).unwrap_or(UnbondingStake {
+        member: member.clone(),
+        amount: 0,
+        release_at: 0,
+    });
+    unbonding.amount += amount;
+    unbonding.release_at = env.ledger().timestamp() + STAKE_UNBONDING_PERIOD;
+    storage::save(env, &unbonding);
+
+    env.events().publish((UNSTAKE_REQUESTED, member), (amount, unbonding.release_at));
+    Ok(unbonding.release_at)
+}
+
+// Returns unbonded tokens to the member once the unbonding period has passed
+pub fn withdraw_stake(env: &Env, member: Address) -> Result<i128, StakingError> {
+    member.require_auth();
+    let unbonding = get_unbonding(env, member.clone()).ok_or(StakingError::NothingToWithdraw)?;
+    if env.ledger().timestamp() < unbonding.release_at {
+        return Err(StakingError::StakeStillUnbonding);
+    }
+    let token = premium_token(env)?;
+
+    storage::remove(env, &DataKey::Unbonding(member.clone()));
+    token.transfer(&env.current_contract_address(), &member, &unbonding.amount);
+    adjust_total_staked(env, -unbonding.amount);
+
+    env.events().publish((STAKE_WITHDRAWN, member), unbonding.amount);
+    Ok(unbonding.amount)
+}
+
+pub fn get_unbonding(env: &Env, member: Address) -> Option<UnbondingStake> {
+    storage::load(env, &DataKey::Unbonding(member))
+}
+
+// Stake a member could still lose to slashing: active plus unbonding
+pub fn slashable_stake(env: &Env, member: &Address) -> i128 {
+    let staked = storage::load::<User>(env, &DataKey::User(member.clone()))
+        .map_or(0, |user| user.staked_amount);
+    let unbonding = get_unbonding(env, member.clone()).map_or(0, |unbonding| unbonding.amount);
+    staked + unbonding
+}
+
+// Takes up to `slash.amount` from the member's stake, active stake first and then anything
+// unbonding, and credits it to the safety pool. Only reachable through an executed proposal.
+pub fn execute_stake_slash(env: &Env, executor: Address, slash: StakeSlash) -> Result<i128, StakingError> {
+    if slash.amount <= 0 {
+        return Err(StakingError::InvalidAmount);
+    }
+    let mut user = load_user(env, &slash.member)?;
+
+    let from_stake = slash.amount.min(user.staked_amount);
+    let mut slashed = from_stake;
+    if from_stake > 0 {
+        user.staked_amount -= from_stake;
+        storage::save(env, &user);
+        refresh_vote_weight(env, &user);
+    }
+
+    if let Some(mut unbonding) = get_unbonding(env, slash.member.clone()) {
+        let from_unbonding = (slash.amount - slashed).min(unbonding.amount);
+        unbonding.amount -= from_unbonding;
+        slashed += from_unbonding;
+        if unbonding.amount == 0 {
+            storage::remove(env, &DataKey::Unbonding(slash.member.clone()));
+        } else {
+            storage::save(env, &unbonding);
+        }
+    }
+
+    if slashed == 0 {
+        return Err(StakingError::InsufficientStake);
+    }
+    adjust_total_staked(env, -slashed);
+    FinancialManagementService::record_slashed_stake(env, slashed);
+
+    env.events().publish((STAKE_SLASHED, slash.member), (executor, slashed));
+    Ok(slashed)
+}
+
+// Tokens the contract holds for stakers, active and unbonding. They never belong to the
+// safety pool, so payouts and token changes must leave them alone.
+pub fn total_staked(env: &Env) -> i128 {
+    env.storage().instance().get(&TOTAL_STAKED).unwrap_or(0)
+}
+
+fn adjust_total_staked(env: &Env, change: i128) {
+    env.storage().instance().set(&TOTAL_STAKED, &(total_staked(env) + change));
+}
+
+fn load_user(env: &Env, member: &Address) -> Result<User, StakingError> {
+    storage::load(env, &DataKey::User(member.clone())).ok_or(StakingError::UserNotFound)
+}
+
+fn premium_token(env: &Env) -> Result<token::Client<'_>, StakingError> {
+    let token_address = FinancialManagementService::get_premium_token(env)
+        .ok_or(StakingError::PaymentFailed)?;
+    Ok(token::Client::new(env, &token_address))
+}
@@ -1,7 +1,9 @@
-use soroban_sdk::{Env, Address,  Vec};
-use crate::constant::{EVENT_SUB_CRT, EVENT_SUB_PAY, EVENT_SUB_SUS};
-use crate::state::{DataKey, Subscription, SubscriptionStatus, Policy};
+use soroban_sdk::{token, Env, Address};
+use crate::constant::{EVENT_SUB_CRT, EVENT_SUB_SUS, PREMIUM_PAID};
+use crate::state::{DataKey, Subscription, SubscriptionStatus, Policy, Payment};
 use crate::instructions::user_management::is_user_approved;
+use crate::storage::{self, next_id};
+use crate::instructions::financial_management::FinancialManagementService;
 
 #[derive(Debug)]
 pub enum SubscriptionManagementError {
@@ -12,7 +14,7 @@ pub enum SubscriptionManagementError {
     InsufficientFunds,
     SubscriptionNotActive,
     PaymentFailed,
-    InvalidPolicy,
+    PolicyNotActive,
 }
 
 pub fn create_subscription(
@@ -20,19 +22,19 @@ pub fn create_subscription(
     subscriber: Address,
     policy_id: u64,
     start_date: u64,
-    premium_amount: i128,
 ) -> Result<u64, SubscriptionManagementError> {
+    subscriber.require_auth();
     
     if !is_user_approved(env, &subscriber) {
         return Err(SubscriptionManagementError::Unauthorized);
     }
 
     
-    let policy = env.storage().instance().get::<_, Policy>(&DataKey::Policy(policy_id))
-        .ok_or(SubscriptionManagementError::InvalidPolicy)?;
+    let policy = storage::load::<Policy>(env, &DataKey::Policy(policy_id))
+        .ok_or(SubscriptionManagementError::PolicyNotFound)?;
 
     if policy.status != crate::state::PolicyStatus::Active {
-        return Err(SubscriptionManagementError::InvalidPolicy);
+        return Err(SubscriptionManagementError::PolicyNotActive);
     }
 
     
@@ -40,12 +42,7 @@ pub fn create_subscription(
         return Err(SubscriptionManagementError::InvalidSubscriptionData);
     }
 
-    
-    if premium_amount <= 0 {
-        return Err(SubscriptionManagementError::InvalidSubscriptionData);
-    }
-
-    let subscription_id = env.ledger().sequence() as u64;
+    let subscription_id = next_id(env, DataKey::LatestSubscriptionId);
     let subscription = Subscription {
         id: subscription_id,
         policy_id,
@@ -60,11 +57,11 @@ pub fn create_subscription(
         total_premiums_paid: 0,
     };
 
-    env.storage().instance().set(&DataKey::Subscription(subscription_id), &subscription);
+    storage::save(env, &subscription);
     
     env.events().publish(
         (EVENT_SUB_CRT, subscription_id),
-        (subscriber, policy_id, premium_amount)
+        (subscriber, policy_id, policy.params.premium_amount)
     );
 
     Ok(subscription_id)
@@ -76,12 +73,13 @@ pub fn update_subscription(
     updater: Address,
     new_start_date: Option<u64>,
 ) -> Result<bool, SubscriptionManagementError> {
+    updater.require_auth();
     let subscription_key = DataKey::Subscription(subscription_id);
-    let mut subscription = env.storage().instance().get::<_, Subscription>(&subscription_key)
+    let mut subscription = storage::load::<Subscription>(env, &subscription_key)
         .ok_or(SubscriptionManagementError::SubscriptionNotFound)?;
 
-    
-    if subscription.subscriber != updater && !is_user_approved(env, &updater) {
+    // Only the subscriber may reschedule their own cover
+    if subscription.subscriber != updater {
         return Err(SubscriptionManagementError::Unauthorized);
     }
 
@@ -99,7 +97,7 @@ pub fn update_subscription(
         }
     }
 
-    env.storage().instance().set(&subscription_key, &subscription);
+    storage::save(env, &subscription);
 
     env.events().publish(
         (EVENT_SUB_CRT, subscription_id),
@@ -114,12 +112,13 @@ pub fn cancel_subscription(
     subscription_id: u64,
     canceller: Address,
 ) -> Result<bool, SubscriptionManagementError> {
+    canceller.require_auth();
     let subscription_key = DataKey::Subscription(subscription_id);
-    let mut subscription = env.storage().instance().get::<_, Subscription>(&subscription_key)
+    let mut subscription = storage::load::<Subscription>(env, &subscription_key)
         .ok_or(SubscriptionManagementError::SubscriptionNotFound)?;
 
-    
-    if subscription.subscriber != canceller && !is_user_approved(env, &canceller) {
+    // Only the subscriber may cancel their own cover
+    if subscription.subscriber != canceller {
         return Err(SubscriptionManagementError::Unauthorized);
     }
 
@@ -129,7 +128,7 @@ pub fn cancel_subscription(
     }
 
     subscription.status = SubscriptionStatus::Cancelled;
-    env.storage().instance().set(&subscription_key, &subscription);
+    storage::save(env, &subscription);
 
     env.events().publish(
         (EVENT_SUB_SUS, subscription_id),
@@ -143,10 +142,12 @@ pub fn process_payment(
     env: &Env,
     subscription_id: u64,
     payer: Address,
-    amount: i128,
 ) -> Result<bool, SubscriptionManagementError> {
+    // The premium is pulled from the payer's balance, so the payer must sign
+    payer.require_auth();
+
     let subscription_key = DataKey::Subscription(subscription_id);
-    let mut subscription = env.storage().instance().get::<_, Subscription>(&subscription_key)
+    let mut subscription = storage::load::<Subscription>(env, &subscription_key)
         .ok_or(SubscriptionManagementError::SubscriptionNotFound)?;
 
     
@@ -159,19 +160,47 @@ pub fn process_payment(
         return Err(SubscriptionManagementError::SubscriptionNotActive);
     }
 
-    
+    let policy = storage::load::<Policy>(env, &DataKey::Policy(subscription.policy_id))
+        .ok_or(SubscriptionManagementError::PolicyNotFound)?;
+
+    let token_address = FinancialManagementService::get_premium_token(env)
+        .ok_or(SubscriptionManagementError::PaymentFailed)?;
+
+    // Late payments carry the platform penalty on top of the weekly premium
+    let premium = policy.params.premium_amount;
+    let penalty = if env.ledger().timestamp() > subscription.next_payment_due {
+        let config = FinancialManagementService::get_platform_config(env);
+        premium * config.penalty_rate as i128 / 10000
+    } else {
+        0
+    };
+    let amount = premium + penalty;
+
+    let token = token::Client::new(env, &token_address);
+    if token.balance(&payer) < amount {
+        return Err(SubscriptionManagementError::InsufficientFunds);
+    }
+    token.transfer(&payer, &env.current_contract_address(), &amount);
+
     subscription.last_payment_date = env.ledger().timestamp();
     subscription.weeks_paid += 1;
     subscription.total_premiums_paid += amount;
-    
-    
-    subscription.next_payment_due = subscription.next_payment_due + (7 * 24 * 60 * 60);
+    subscription.next_payment_due += 7 * 24 * 60 * 60;
 
-    env.storage().instance().set(&subscription_key, &subscription);
+    storage::save(env, &subscription);
+
+    FinancialManagementService::record_premium_payment(env, Payment {
+        user: payer.clone(),
+        plan_id: subscription.policy_id,
+        amount,
+        week_number: subscription.weeks_paid,
+        payment_date: env.ledger().timestamp(),
+        penalty_applied: penalty,
+    });
 
     env.events().publish(
-        (EVENT_SUB_PAY, subscription_id),
-        (payer, amount, "payment processed")
+        (PREMIUM_PAID, subscription_id),
+        (payer, amount, subscription.weeks_paid)
     );
 
     Ok(true)
@@ -183,8 +212,9 @@ pub fn renew_subscription(
     renewer: Address,
     new_start_date: u64,
 ) -> Result<bool, SubscriptionManagementError> {
+    renewer.require_auth();
     let subscription_key = DataKey::Subscription(subscription_id);
-    let mut subscription = env.storage().instance().get::<_, Subscription>(&subscription_key)
+    let mut subscription = storage::load::<Subscription>(env, &subscription_key)
         .ok_or(SubscriptionManagementError::SubscriptionNotFound)?;
 
     
@@ -202,7 +232,7 @@ pub fn renew_subscription(
     subscription.next_payment_due = new_start_date + (7 * 24 * 60 * 60);
     subscription.grace_period_end = new_start_date + (14 * 24 * 60 * 60);
     
-    env.storage().instance().set(&subscription_key, &subscription);
+    storage::save(env, &subscription);
 
     env.events().publish(
         (EVENT_SUB_CRT, subscription_id),
@@ -213,80 +243,6 @@ pub fn renew_subscription(
 }
 
 pub fn get_subscription(env: &Env, subscription_id: u64) -> Result<Subscription, SubscriptionManagementError> {
-    env.storage().instance().get(&DataKey::Subscription(subscription_id))
+    storage::load(env, &DataKey::Subscription(subscription_id))
         .ok_or(SubscriptionManagementError::SubscriptionNotFound)
 }
-
-pub fn get_user_subscriptions(env: &Env, user: Address) -> Result<Vec<u64>, SubscriptionManagementError> {
-    // This is a simplified implementation - in a real contract you might want to maintain an index
-    // For now, we'll return an empty vector as this would require more complex storage patterns
-    //TODO: Implement this
-    Ok(Vec::new(&env))
-}
-
-pub fn get_active_subscriptions(env: &Env) -> Result<Vec<u64>, SubscriptionManagementError> {
-    // This is a simplified implementation - in a real contract you might want to maintain an index
-    // For now, we'll return an empty vector as this would require more complex storage patterns
-    //TODO: Implement this
-    Ok(Vec::new(&env))
-}
-
-pub fn calculate_premium(
-    env: &Env,
-    policy_id: u64,
-    coverage_amount: i128,
-    duration_days: u32,
-    risk_score: u32,
-) -> i128 {
-    let policy = match env.storage().instance().get::<_, Policy>(&DataKey::Policy(policy_id)) {
-        Some(p) => p,
-        None => return 0,
-    };
-
-    let base_rate = policy.params.interest_rate;
-    let risk_multiplier = 100 + (risk_score as i128 * 2); 
-    let duration_multiplier = (duration_days as i128 * 100) / 365; 
-
-    (coverage_amount * base_rate as i128 * risk_multiplier * duration_multiplier) / (100 * 100 * 100)
-}
-
-pub fn validate_subscription_eligibility(
-    env: &Env,
-    user: Address,
-    policy_id: u64,
-) -> Result<bool, SubscriptionManagementError> {
-    
-    if !is_user_approved(env, &user) {
-        return Err(SubscriptionManagementError::Unauthorized);
-    }
-
-    
-    let policy = env.storage().instance().get::<_, Policy>(&DataKey::Policy(policy_id))
-        .ok_or(SubscriptionManagementError::InvalidPolicy)?;
-
-    if policy.status != crate::state::PolicyStatus::Active {
-        return Err(SubscriptionManagementError::InvalidPolicy);
-    }
-
-    // Check if user already has an active subscription for this policy
-    //TODO: Implement this  
-    // This would require maintaining an index in a real implementation
-    
-    Ok(true)
-}
-
-
-pub fn is_in_grace_period(env: &Env, subscription: &Subscription) -> bool {
-    env.ledger().timestamp() <= subscription.grace_period_end
-}
-
-
-pub fn can_renew_subscription(env: &Env, subscription: &Subscription) -> bool {
-    subscription.status == SubscriptionStatus::Active && 
-    env.ledger().timestamp() >= subscription.start_date - (30 * 24 * 60 * 60) 
-}
-
-
-pub fn is_payment_overdue(env: &Env, subscription: &Subscription) -> bool {
-    env.ledger().timestamp() > subscription.next_payment_due + (7 * 24 * 60 * 60) 
-}
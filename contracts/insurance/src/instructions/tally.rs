@@ -0,0 +1,49 @@
+use soroban_sdk::Env;
+use crate::constant::{CONVICTION_DAILY_GROWTH, CONVICTION_MAX_GROWTH, SECONDS_PER_DAY};
+use crate::state::{DataKey, Proposal, TallyMode, VoteKey, VoteRecord, VoteWeight};
+use crate::storage;
+
+// The part of a member's weight that counts under the tally mode. Quadratic takes the
+// integer square root of the full weight, so each extra unit of say costs more to earn.
+pub fn counted_weight(weight: &VoteWeight, mode: &TallyMode) -> u32 {
+    match mode {
+        TallyMode::Linear | TallyMode::Conviction => weight.total(),
+        TallyMode::Quadratic => isqrt(weight.total()),
+    }
+}
+
+pub fn isqrt(value: u32) -> u32 {
+    let mut root = 0;
+    while (root + 1) * (root + 1) <= value {
+        root += 1;
+    }
+    root
+}
+
+// Conviction multiplier in basis points: 1x, plus CONVICTION_DAILY_GROWTH for every full
+// day the vote was held, capped at CONVICTION_MAX_GROWTH on top
+pub fn conviction_multiplier(held: u64) -> u32 {
+    let days = (held / SECONDS_PER_DAY).min(u32::MAX as u64) as u32;
+    10000 + days.saturating_mul(CONVICTION_DAILY_GROWTH).min(CONVICTION_MAX_GROWTH)
+}
+
+// Recounts the tallies of a conviction proposal once voting has closed, scaling each vote
+// (with any weight it carried for delegators) by how long before the close it was cast.
+// Quorum is checked against the tallies from before this runs. The caller saves the proposal.
+pub fn apply_conviction(env: &Env, proposal: &mut Proposal) {
+    let (mut votes_for, mut votes_against) = (0_u64, 0_u64);
+    for voter in proposal.voters.iter() {
+        let key = DataKey::Vote(VoteKey { proposal_id: proposal.id, voter });
+        if let Some(vote) = storage::load::<VoteRecord>(env, &key) {
+            let held = proposal.voting_period_end.saturating_sub(vote.timestamp);
+            let weight = (vote.weight.total() + vote.delegated_weight) as u64 * conviction_multiplier(held) as u64;
+            if vote.vote {
+                votes_for += weight;
+            } else {
+                votes_against += weight;
+            }
+        }
+    }
+    proposal.votes_for = (votes_for / 10000) as u32;
+    proposal.votes_against = (votes_against / 10000) as u32;
+}
@@ -1,27 +1,43 @@
 use soroban_sdk::xdr::ToXdr;
-use soroban_sdk::{symbol_short, Address, Env, IntoVal, String, TryIntoVal, Vec as SdkVec};
-use crate::constant::{CONFIG_MIN_QUORUM, USR_REG, USR_BAN, USR_DAO, REP_UPD};
-use crate::state::{DataKey, User, UserStatus, ProposalType, Proposal, ProposalStatus};
+use soroban_sdk::{Address, Env, String, Vec as SdkVec};
+use crate::constant::{
+    COUNCIL_MEMBERS, USR_REG, USR_BAN, USR_DAO, USR_HOUSEHOLD, REP_UPD, EVENT_DAO_REM, MIN_COUNCIL_SIZE, VOTE_BASE_WEIGHT,
+    VOTE_REPUTATION_STEP, VOTE_MAX_REPUTATION_BOOST, VOTE_STAKE_STEP, VOTE_MAX_STAKE_BOOST,
+};
+use crate::state::{DataKey, User, UserStatus, ProposalType, ProposalAction, VoteWeight};
+use crate::error::Error;
+use crate::storage;
+use crate::instructions::dao_governance::open_proposal;
+use crate::instructions::admin::is_admin;
+use crate::instructions::financial_management::FinancialManagementService;
 
 #[derive(Debug)]
 pub enum UserManagementError {
     UserAlreadyExists,
     UserNotFound,
     Unauthorized,
-    VotingPeriodEnded,
-    AlreadyVoted,
+    CouncilFull,
+    CouncilTooSmall,
+    NotCouncilMember,
 }
 
 pub fn register_user(env: &Env, user: Address, name: Option<String>) -> Result<bool, UserManagementError> {
+    user.require_auth();
     let user_key = DataKey::User(user.clone());
 
-    if env.storage().instance().has(&user_key) {
+    if storage::has(env, &user_key) {
         return Err(UserManagementError::UserAlreadyExists);
     }   
 
-    let new_user = User {
+    storage::save(env, &new_user(env, &user, name));
+    env.events().publish((USR_REG, user.clone()), "new user created");
+    Ok(true)
+}
+
+fn new_user(env: &Env, user: &Address, name: Option<String>) -> User {
+    User {
         address: user.clone(),
-        name: name.unwrap_or(String::from_str(&env, "")),
+        name: name.unwrap_or(String::from_str(env, "")),
         credit_score: 100,         // Initial credit score
         status: UserStatus::Active,
         join_date: env.ledger().timestamp(),
@@ -31,171 +47,230 @@ pub fn register_user(env: &Env, user: Address, name: Option<String>) -> Result<b
         last_vote_timestamp: 0,   // Never voted
         subscribed_plan: None,     // No initial subscription
         village_contributions: 0,  // No initial contributions
-    };
+        household: None,
+    }
+}
 
-    env.storage().instance().set(&user_key, &new_user);
-    env.events().publish((USR_REG, user.clone()), "new user created");
-    Ok(true)
+// Grants council membership, registering the address first if it has never signed up.
+// Callers are responsible for authorization.
+pub fn enroll_council_member(env: &Env, member: &Address) {
+    let mut user = storage::load::<User>(env, &DataKey::User(member.clone()))
+        .unwrap_or_else(|| new_user(env, member, None));
+    user.is_dao_member = true;
+    user.status = UserStatus::Active;
+    storage::save(env, &user);
+    refresh_vote_weight(env, &user);
+
+    let mut council = get_council_members(env);
+    if !council.contains(member) {
+        council.push_back(member.clone());
+        env.storage().instance().set(&COUNCIL_MEMBERS, &council);
+    }
+
+    env.events().publish((USR_DAO, member.clone()), "added to DAO");
+}
+
+pub fn get_council_members(env: &Env) -> SdkVec<Address> {
+    env.storage().instance().get(&COUNCIL_MEMBERS).unwrap_or_else(|| SdkVec::new(env))
 }
 
 pub fn approve_user(env: &Env, user: Address, approver: Address) -> Result<bool, UserManagementError> {
+    approver.require_auth();
     // Check if approver is DAO member
     if !check_dao_member(env, &approver)? {
         return Err(UserManagementError::Unauthorized);
     }
 
-    let user_key = DataKey::User(user.clone());
-    let mut user_data = env.storage().instance().get::<_, User>(&user_key)
-        .ok_or(UserManagementError::UserNotFound)?;
+    execute_user_approval(env, user)
+}
+
+// Activates a registered user.
+pub fn execute_user_approval(env: &Env, user: Address) -> Result<bool, UserManagementError> {
+    let mut user_data = get_user(env, user.clone())?;
+    // Only a `UserBan` is final; nothing lifts it
+    if user_data.status == UserStatus::Banned {
+        return Err(UserManagementError::Unauthorized);
+    }
 
     user_data.status = UserStatus::Active;
-    env.storage().instance().set(&user_key, &user_data);
+    storage::save(env, &user_data);
     
     env.events().publish((USR_REG, user.clone()), "user approved");
     Ok(true)
 }
 
 pub fn suspend_user(env: &Env, user: Address, admin: Address, reason: String) -> Result<bool, UserManagementError> {
+    admin.require_auth();
     // Check if admin is DAO member
     if !check_dao_member(env, &admin)? {
         return Err(UserManagementError::Unauthorized);
     }
 
     let user_key = DataKey::User(user.clone());
-    let mut user_data = env.storage().instance().get::<_, User>(&user_key)
+    let mut user_data = storage::load::<User>(env, &user_key)
         .ok_or(UserManagementError::UserNotFound)?;
+    if user_data.status == UserStatus::Banned {
+        return Err(UserManagementError::Unauthorized);
+    }
 
     user_data.status = UserStatus::Pending;
-    env.storage().instance().set(&user_key, &user_data);
+    storage::save(env, &user_data);
     
     env.events().publish((USR_BAN, user.clone()), reason);
     Ok(true)
 }
 
-pub fn ban_user(env: &Env, user: Address, admin: Address, reason: String) -> Result<bool, UserManagementError> {
-    // Check if admin is DAO member
-    if !check_dao_member(env, &admin)? {
-        return Err(UserManagementError::Unauthorized);
-    }
-
-    let user_key = DataKey::User(user.clone());
-    let mut user_data = env.storage().instance().get::<_, User>(&user_key)
-        .ok_or(UserManagementError::UserNotFound)?;
+// Bans a user. A banned council member loses their voting rights at once. Their seat is given up too
+// while the council can spare it; otherwise it stays, inert, until a `RemoveCouncilMember`
+// proposal clears it once the council has grown.
+pub fn execute_user_ban(env: &Env, user: Address, reason: String) -> Result<bool, UserManagementError> {
+    let mut user_data = get_user(env, user.clone())?;
 
+    if user_data.is_dao_member && get_council_members(env).len() > MIN_COUNCIL_SIZE {
+        revoke_council_seat(env, &mut user_data, reason.clone())?;
+    }
     user_data.status = UserStatus::Banned;
-    env.storage().instance().set(&user_key, &user_data);
+    storage::save(env, &user_data);
     
     env.events().publish((USR_BAN, user.clone()), reason);
     Ok(true)
 }
 
+// Direct appointment is reserved for the bootstrap admin until the role is renounced;
+// the council itself adds members through `MembershipChange` proposals.
 pub fn add_council_member(env: &Env, new_member: Address, appointer: Address) -> Result<bool, UserManagementError> {
-    // Check if appointer is DAO member
-    if !check_dao_member(env, &appointer)? {
+    appointer.require_auth();
+    if !is_admin(env, &appointer) {
         return Err(UserManagementError::Unauthorized);
     }
 
-    let user_key = DataKey::User(new_member.clone());
-    let mut user_data = env.storage().instance().get::<_, User>(&user_key)
-        .ok_or(UserManagementError::UserNotFound)?;
+    execute_membership_change(env, new_member)
+}
 
-    user_data.is_dao_member = true;
-    env.storage().instance().set(&user_key, &user_data);
-    
-    env.events().publish((USR_DAO, new_member.clone()), "added to DAO");
+// Grants council membership to a registered user, up to the platform's `council_size`.
+// Banned users stay off the council and sitting members can't be added twice.
+pub fn execute_membership_change(env: &Env, new_member: Address) -> Result<bool, UserManagementError> {
+    let user = get_user(env, new_member.clone())?;
+    if user.status == UserStatus::Banned {
+        return Err(UserManagementError::Unauthorized);
+    }
+    if user.is_dao_member {
+        return Err(UserManagementError::UserAlreadyExists);
+    }
+    let council_size = FinancialManagementService::get_platform_config(env).council_size;
+    if get_council_members(env).len() as u64 >= council_size {
+        return Err(UserManagementError::CouncilFull);
+    }
+
+    enroll_council_member(env, &new_member);
     Ok(true)
 }
 
-pub fn propose_user_ban(env: &Env, user: Address, dao_member: Address, reason: String) -> Result<bool, UserManagementError> {
-    // Check if banner is DAO member
-    if !check_dao_member(env, &dao_member)? {
-        return Err(UserManagementError::Unauthorized);
+// Takes a member off the council.
+pub fn execute_membership_removal(env: &Env, member: Address, reason: String) -> Result<bool, UserManagementError> {
+    let mut user = get_user(env, member)?;
+    if !user.is_dao_member {
+        return Err(UserManagementError::NotCouncilMember);
     }
 
-    // Create ban proposal
-    let proposal = Proposal {
-        id: env.ledger().sequence() as u64,
-        proposer: dao_member.clone(),
-        proposal_type: ProposalType::UserBan,
-        title: String::from_str(&env, "User Ban"),
-        description: reason,
-        start_time: env.ledger().timestamp(),
-        end_time: env.ledger().timestamp() + 604800, // 7 days
-        yes_votes: 0,     // Each vote counts as 1
-        no_votes: 0,      // Each vote counts as 1
-        status: ProposalStatus::Open,
-        execution_data: user.clone().to_object().to_xdr(&env).try_into().unwrap(),
-        required_quorum: CONFIG_MIN_QUORUM, // Standard quorum for bans
-        voters: SdkVec::new(&env),
-        voting_period_end: env.ledger().timestamp() + 604800,
-        votes_for: 0,
-        votes_against: 0,
-        quorum_required: CONFIG_MIN_QUORUM as i128,
-        created_date: env.ledger().timestamp(),
-    };
+    revoke_council_seat(env, &mut user, reason)?;
+    storage::save(env, &user);
+    Ok(true)
+}
 
-    env.storage().instance().set(&DataKey::DAOProposal(proposal.id), &proposal);
+// Clears the member's seat and drops them from the council list. The caller saves the user.
+fn revoke_council_seat(env: &Env, user: &mut User, reason: String) -> Result<(), UserManagementError> {
+    let mut council = get_council_members(env);
+    if council.len() <= MIN_COUNCIL_SIZE {
+        return Err(UserManagementError::CouncilTooSmall);
+    }
+
+    if let Some(position) = council.first_index_of(&user.address) {
+        council.remove(position);
+        env.storage().instance().set(&COUNCIL_MEMBERS, &council);
+    }
+    user.is_dao_member = false;
+
+    env.events().publish((EVENT_DAO_REM, user.address.clone()), reason);
+    Ok(())
+}
+
+// Bans only take effect through a `UserBan` proposal; this opens one with the reason as
+// its description and returns its id.
+pub fn propose_user_ban(env: &Env, user: Address, dao_member: Address, reason: String) -> Result<u64, Error> {
+    dao_member.require_auth();
+    // Check if banner is DAO member
+    if !check_dao_member(env, &dao_member)? {
+        return Err(UserManagementError::Unauthorized.into());
+    }
+    get_user(env, user.clone())?;
+
+    let proposal_id = open_proposal(
+        env,
+        dao_member.clone(),
+        ProposalType::UserBan,
+        String::from_str(env, "User Ban"),
+        reason,
+        ProposalAction::BanUser(user.clone()).to_xdr(env),
+        None,
+    )?;
     
     env.events().publish(
         (USR_BAN, user.clone()),
-        (dao_member, proposal.id)
+        (dao_member, proposal_id)
     );
-    Ok(true)
+    Ok(proposal_id)
 }
 
-pub fn execute_user_status_change(env: &Env, proposal_id: u64) -> Result<bool, UserManagementError> {
-    let proposal = env.storage().instance().get::<_, Proposal>(&DataKey::DAOProposal(proposal_id))
-        .ok_or(UserManagementError::UserNotFound)?;
-    
-    if proposal.status != ProposalStatus::Passed {
+// Records which household a user belongs to, or clears it. Claims may not be assessed by
+// anyone from the claimer's household.
+pub fn set_household(
+    env: &Env,
+    user: Address,
+    household: Option<u64>,
+    council_member: Address,
+) -> Result<bool, UserManagementError> {
+    council_member.require_auth();
+    if !check_dao_member(env, &council_member)? {
         return Err(UserManagementError::Unauthorized);
     }
 
-    let user_address: Address = proposal.execution_data.try_into_val(env).unwrap();
-    let mut user = env.storage().instance().get::<_, User>(&DataKey::User(user_address.clone()))
-        .ok_or(UserManagementError::UserNotFound)?;
-
-    match proposal.proposal_type {
-        ProposalType::UserBan => {
-            user.status = UserStatus::Banned;
-            env.events().publish((USR_BAN, user_address.clone()), proposal_id);
-        },
-        ProposalType::MembershipChange => {
-            user.is_dao_member = true;
-            env.events().publish((USR_DAO, user_address.clone()), proposal_id);
-        },
-        _ => return Err(UserManagementError::Unauthorized),
-    }
+    let mut user_data = get_user(env, user.clone())?;
+    user_data.household = household;
+    storage::save(env, &user_data);
 
-    env.storage().instance().set(&DataKey::User(user_address), &user);
+    env.events().publish((USR_HOUSEHOLD, user), household);
     Ok(true)
 }
 
 pub fn check_dao_member(env: &Env, address: &Address) -> Result<bool, UserManagementError> {
-    let user = env.storage().instance().get::<_, User>(&DataKey::User(address.clone()))
+    let user = storage::load::<User>(env, &DataKey::User(address.clone()))
         .ok_or(UserManagementError::UserNotFound)?;
-    Ok(user.is_dao_member)
+    Ok(user.is_dao_member && user.status == UserStatus::Active)
 }
 
+#[allow(dead_code)]
 pub fn update_user_reputation(env: &Env, user: Address, change: i32, dao_member: Address) -> Result<bool, UserManagementError> {
+    dao_member.require_auth();
     // Only DAO members can update reputation
     if !check_dao_member(env, &dao_member)? {
         return Err(UserManagementError::Unauthorized);
     }
 
     let user_key = DataKey::User(user.clone());
-    let mut user_data = env.storage().instance().get::<_, User>(&user_key)
+    let mut user_data = storage::load::<User>(env, &user_key)
         .ok_or(UserManagementError::UserNotFound)?;
 
-    // Update reputation (0-500 range) - just for gamification now
+    // Update reputation (0-500 range); it feeds the member's vote weight
     let new_reputation = if change < 0 {
-        user_data.reputation_score.saturating_sub((-change) as u32)
+        user_data.reputation_score.saturating_sub(change.unsigned_abs())
     } else {
         user_data.reputation_score.saturating_add(change as u32)
     };
     user_data.reputation_score = new_reputation.min(500);
 
-    env.storage().instance().set(&user_key, &user_data);
+    storage::save(env, &user_data);
+    refresh_vote_weight(env, &user_data);
     
     env.events().publish(
         (REP_UPD, user.clone()),
@@ -204,44 +279,39 @@ pub fn update_user_reputation(env: &Env, user: Address, change: i32, dao_member:
     Ok(true)
 }
 
-pub fn get_user(env: &Env, user: Address) -> Result<User, UserManagementError> {
-    env.storage().instance().get(&DataKey::User(user))
-        .ok_or(UserManagementError::UserNotFound)
+// Voting power derived from reputation and stake, capped by the VOTE_* constants
+pub fn calculate_vote_weight(env: &Env, user: &User) -> VoteWeight {
+    let reputation_boost = (user.reputation_score / VOTE_REPUTATION_STEP).min(VOTE_MAX_REPUTATION_BOOST);
+    let stake_steps = user.staked_amount.max(0) / VOTE_STAKE_STEP;
+    let stake_boost = stake_steps.min(VOTE_MAX_STAKE_BOOST as i128) as u32;
+
+    VoteWeight {
+        voter: user.address.clone(),
+        base_weight: VOTE_BASE_WEIGHT,
+        reputation_boost,
+        stake_boost,
+        last_updated: env.ledger().timestamp(),
+    }
 }
 
-pub fn propose_user_dao(env: &Env, new_member: Address, appointer: Address, reason: String) -> Result<bool, UserManagementError>  {
-    if !check_dao_member(env, &appointer)? {
-        return Err(UserManagementError::Unauthorized);
-    }
+// Recomputes and caches the weight; call whenever reputation or stake changes.
+pub fn refresh_vote_weight(env: &Env, user: &User) -> VoteWeight {
+    let weight = calculate_vote_weight(env, user);
+    storage::save(env, &weight);
+    weight
+}
 
-     let proposal = Proposal {
-        id: env.ledger().sequence() as u64,
-        proposer: appointer.clone(),
-        proposal_type: ProposalType::MembershipChange,
-        title: String::from_str(&env, "add to dao"),
-        description: reason,
-        start_time: env.ledger().timestamp(),
-        end_time: env.ledger().timestamp() + 604800, // 7 days
-        yes_votes: 0,     // Each vote counts as 1
-        no_votes: 0,      // Each vote counts as 1
-        status: ProposalStatus::Open,
-        execution_data: new_member.clone().to_object().to_xdr(&env).try_into().unwrap(),
-        required_quorum: CONFIG_MIN_QUORUM, // Standard quorum for bans
-        voters: SdkVec::new(&env),
-        voting_period_end: env.ledger().timestamp() + 604800,
-        votes_for: 0,
-        votes_against: 0,
-        quorum_required: CONFIG_MIN_QUORUM as i128,
-        created_date: env.ledger().timestamp(),
-    };
+// Cached weight, falling back to a fresh calculation for members who have none yet
+pub fn get_vote_weight(env: &Env, address: Address) -> Result<VoteWeight, UserManagementError> {
+    if let Some(weight) = storage::load::<VoteWeight>(env, &DataKey::VoteWeight(address.clone())) {
+        return Ok(weight);
+    }
+    Ok(calculate_vote_weight(env, &get_user(env, address)?))
+}
 
-    env.storage().instance().set(&DataKey::DAOProposal(proposal.id), &proposal);
-    
-    env.events().publish(
-        (USR_DAO, new_member.clone()),
-        (appointer, proposal.id)
-    );
-    Ok(true)
+pub fn get_user(env: &Env, user: Address) -> Result<User, UserManagementError> {
+    storage::load(env, &DataKey::User(user))
+        .ok_or(UserManagementError::UserNotFound)
 }
 
 // Helper functions for other modules
@@ -255,7 +325,7 @@ pub fn is_user_approved(env: &Env, address: &Address) -> bool {
 
 pub fn is_council_member(env: &Env, address: &Address) -> bool {
     if let Ok(user) = get_user(env, address.clone()) {
-        user.is_dao_member
+        user.is_dao_member && user.status == UserStatus::Active
     } else {
         false
     }
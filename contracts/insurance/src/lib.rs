@@ -1,12 +1,16 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, Address, Bytes, Env, String};
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, String, Vec};
 
 mod state;
 mod instructions;
 mod constant;
+mod error;
+mod storage;
+mod test;
 
 use state::*;
 use instructions::*;
+pub use error::Error;
 
 #[contract]
 pub struct VillageMicroInsuranceContract;
@@ -15,25 +19,57 @@ pub struct VillageMicroInsuranceContract;
 #[contractimpl]
 impl VillageMicroInsuranceContract {
     
+    // Runs atomically with deployment, so setup can never be front-run
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        premium_token: Address,
+        council: Vec<Address>,
+        config: PlatformConfig,
+        minimum_reserve: i128,
+        reserve_ratio: u64,
+    ) -> Result<(), Error> {
+        Ok(admin::initialize(&env, admin, premium_token, council, config, minimum_reserve, reserve_ratio)?)
+    }
+
+    pub fn transfer_admin(env: Env, new_admin: Address) -> Result<bool, Error> {
+        Ok(admin::transfer_admin(&env, new_admin)?)
+    }
+
+    pub fn renounce_admin(env: Env) -> Result<bool, Error> {
+        Ok(admin::renounce_admin(&env)?)
+    }
+
+    pub fn get_admin(env: Env) -> Option<Address> {
+        admin::get_admin(&env)
+    }
+
+    pub fn get_council_members(env: Env) -> Vec<Address> {
+        user_management::get_council_members(&env)
+    }
     
-    pub fn register_user(env: Env, user: Address, name: Option<String>) -> bool {
-        user_management::register_user(&env, user, name).unwrap()
+    pub fn register_user(env: Env, user: Address, name: Option<String>) -> Result<bool, Error> {
+        Ok(user_management::register_user(&env, user, name)?)
+    }
+
+    pub fn approve_user(env: Env, user: Address, approver: Address) -> Result<bool, Error> {
+        Ok(user_management::approve_user(&env, user, approver)?)
     }
 
-    pub fn approve_user(env: Env, user: Address, approver: Address) -> bool {
-        user_management::approve_user(&env, user, approver).unwrap()
+    pub fn set_household(env: Env, user: Address, household: Option<u64>, council_member: Address) -> Result<bool, Error> {
+        Ok(user_management::set_household(&env, user, household, council_member)?)
     }
 
-    pub fn suspend_user(env: Env, user: Address, admin: Address, reason: String) -> bool {
-        user_management::suspend_user(&env, user, admin, reason).unwrap()
+    pub fn suspend_user(env: Env, user: Address, admin: Address, reason: String) -> Result<bool, Error> {
+        Ok(user_management::suspend_user(&env, user, admin, reason)?)
     }
 
-    pub fn ban_user(env: Env, user: Address, admin: Address, reason: String) -> bool {
-        user_management::ban_user(&env, user, admin, reason).unwrap()
+    pub fn propose_user_ban(env: Env, user: Address, proposer: Address, reason: String) -> Result<u64, Error> {
+        user_management::propose_user_ban(&env, user, proposer, reason)
     }
 
-    pub fn add_council_member(env: Env, new_member: Address, appointer: Address) -> bool {
-        user_management::add_council_member(&env, new_member, appointer).unwrap()
+    pub fn add_council_member(env: Env, new_member: Address, appointer: Address) -> Result<bool, Error> {
+        Ok(user_management::add_council_member(&env, new_member, appointer)?)
     }
 
     
@@ -44,51 +80,295 @@ impl VillageMicroInsuranceContract {
         title: String,
         description: String,
         execution_data: Bytes,
-        required_quorum: u32,
-    ) -> u64 {
-        dao_governance::create_proposal(&env, proposer, proposal_type, title, description, execution_data, required_quorum).unwrap()
+        voting_duration: Option<u64>,
+    ) -> Result<u64, Error> {
+        Ok(dao_governance::create_proposal(&env, proposer, proposal_type, title, description, execution_data, voting_duration)?)
+    }
+
+    pub fn finalize_proposal(env: Env, proposal_id: u64) -> Result<ProposalStatus, Error> {
+        Ok(dao_governance::finalize_proposal(&env, proposal_id)?)
+    }
+
+    pub fn sweep_expired_proposals(env: Env, proposal_ids: Vec<u64>) -> u32 {
+        dao_governance::sweep_expired_proposals(&env, proposal_ids)
+    }
+
+    pub fn cancel_proposal(env: Env, proposal_id: u64, proposer: Address) -> Result<bool, Error> {
+        Ok(dao_governance::cancel_proposal(&env, proposal_id, proposer)?)
+    }
+
+    pub fn veto_proposal(env: Env, proposal_id: u64, member: Address, reason: String) -> Result<ProposalStatus, Error> {
+        Ok(dao_governance::veto_proposal(&env, proposal_id, member, reason)?)
+    }
+
+    pub fn get_proposal_vetoes(env: Env, proposal_id: u64) -> Vec<Address> {
+        dao_governance::get_proposal_vetoes(&env, proposal_id)
+    }
+
+    pub fn get_proposals_by_type(env: Env, proposal_type: ProposalType, start: u32, limit: u32) -> ProposalPage {
+        dao_governance::get_proposals_by_type(&env, proposal_type, start, limit)
+    }
+
+    pub fn get_proposals_by_status(env: Env, listing: ProposalListing, start: u32, limit: u32) -> ProposalPage {
+        dao_governance::get_proposals_by_status(&env, listing, start, limit)
+    }
+
+    pub fn get_proposals_by_proposer(env: Env, proposer: Address, start: u32, limit: u32) -> ProposalPage {
+        dao_governance::get_proposals_by_proposer(&env, proposer, start, limit)
+    }
+
+    pub fn get_active_proposals(env: Env, start: u32, limit: u32) -> ProposalPage {
+        dao_governance::get_active_proposals(&env, start, limit)
+    }
+
+    pub fn get_proposal_config(env: Env, proposal_type: ProposalType) -> ProposalConfig {
+        dao_governance::get_proposal_config(&env, proposal_type)
     }
 
-    pub fn vote_on_proposal(env: Env, proposal_id: u64, voter: Address, vote_for: bool, vote_weight: i128) -> bool {
-        dao_governance::vote_on_proposal(&env, proposal_id, voter, vote_for, vote_weight).unwrap()
+    pub fn vote_on_proposal(
+        env: Env,
+        proposal_id: u64,
+        voter: Address,
+        vote_for: bool,
+        metadata: Option<String>,
+    ) -> Result<bool, Error> {
+        Ok(dao_governance::vote_on_proposal(&env, proposal_id, voter, vote_for, metadata)?)
+    }
+
+    pub fn commit_vote(env: Env, proposal_id: u64, voter: Address, commitment: BytesN<32>) -> Result<bool, Error> {
+        Ok(secret_ballot::commit_vote(&env, proposal_id, voter, commitment)?)
+    }
+
+    pub fn reveal_vote(
+        env: Env,
+        proposal_id: u64,
+        voter: Address,
+        vote_for: bool,
+        salt: BytesN<32>,
+        metadata: Option<String>,
+    ) -> Result<bool, Error> {
+        Ok(secret_ballot::reveal_vote(&env, proposal_id, voter, vote_for, salt, metadata)?)
+    }
+
+    pub fn get_vote_commit(env: Env, proposal_id: u64, voter: Address) -> Option<VoteCommit> {
+        secret_ballot::get_vote_commit(&env, proposal_id, voter)
+    }
+
+    pub fn delegate_vote(env: Env, delegator: Address, delegate: Address, scope: DelegationScope) -> Result<bool, Error> {
+        Ok(delegation::delegate_vote(&env, delegator, delegate, scope)?)
+    }
+
+    pub fn revoke_delegation(env: Env, delegator: Address, scope: DelegationScope) -> Result<bool, Error> {
+        Ok(delegation::revoke_delegation(&env, delegator, scope)?)
+    }
+
+    pub fn get_delegation(env: Env, delegator: Address, scope: DelegationScope) -> Option<VoteDelegation> {
+        delegation::get_delegation(&env, delegator, scope)
+    }
+
+    pub fn stake(env: Env, member: Address, amount: i128) -> Result<bool, Error> {
+        Ok(staking::stake(&env, member, amount)?)
     }
 
-    pub fn execute_proposal(env: Env, proposal_id: u64, executor: Address) -> bool {
-        dao_governance::execute_proposal(&env, proposal_id, executor).unwrap()
+    pub fn request_unstake(env: Env, member: Address, amount: i128) -> Result<u64, Error> {
+        Ok(staking::request_unstake(&env, member, amount)?)
+    }
+
+    pub fn withdraw_stake(env: Env, member: Address) -> Result<i128, Error> {
+        Ok(staking::withdraw_stake(&env, member)?)
+    }
+
+    pub fn get_unbonding(env: Env, member: Address) -> Option<UnbondingStake> {
+        staking::get_unbonding(&env, member)
+    }
+
+    pub fn get_vote_weight(env: Env, member: Address) -> Result<VoteWeight, Error> {
+        Ok(user_management::get_vote_weight(&env, member)?)
+    }
+
+    pub fn get_vote(env: Env, proposal_id: u64, voter: Address) -> Result<VoteRecord, Error> {
+        Ok(dao_governance::get_vote(&env, proposal_id, voter)?)
+    }
+
+    pub fn get_proposal_votes(env: Env, proposal_id: u64, start: u32, limit: u32) -> Result<Vec<VoteRecord>, Error> {
+        Ok(dao_governance::get_proposal_votes(&env, proposal_id, start, limit)?)
+    }
+
+    pub fn get_member_votes(env: Env, member: Address, start: u32, limit: u32) -> Vec<VoteRecord> {
+        dao_governance::get_member_votes(&env, member, start, limit)
+    }
+
+    pub fn execute_proposal(env: Env, proposal_id: u64, executor: Address) -> Result<bool, Error> {
+        proposal_execution::execute_proposal(&env, proposal_id, executor)
     }
 
     
-    pub fn get_user(env: Env, user: Address) -> Option<User> {
-        user_management::get_user(&env, user).ok()
+    pub fn create_policy(
+        env: Env,
+        creator: Address,
+        title: String,
+        description: String,
+        params: PolicyParams,
+    ) -> Result<u64, Error> {
+        Ok(policy_management::create_policy(&env, creator, title, description, params)?)
+    }
+
+    pub fn update_policy(
+        env: Env,
+        updater: Address,
+        policy_id: u64,
+        new_title: Option<String>,
+        new_description: Option<String>,
+        new_params: Option<PolicyParams>,
+    ) -> Result<bool, Error> {
+        Ok(policy_management::update_policy(&env, policy_id, updater, new_title, new_description, new_params)?)
+    }
+
+    pub fn archive_policy(env: Env, archiver: Address, policy_id: u64) -> Result<bool, Error> {
+        Ok(policy_management::archive_policy(&env, policy_id, archiver)?)
+    }
+
+    pub fn delete_policy(env: Env, deleter: Address, policy_id: u64) -> Result<bool, Error> {
+        Ok(policy_management::delete_policy(&env, policy_id, deleter)?)
     }
 
-    pub fn get_proposal(env: Env, proposal_id: u64) -> Option<Proposal> {
-        dao_governance::get_proposal(&env, proposal_id).ok()
+    pub fn validate_policy_eligibility(env: Env, user: Address, policy_id: u64) -> Result<bool, Error> {
+        Ok(policy_management::validate_policy_eligibility(&env, user, policy_id)?)
     }
 
     
-    pub fn get_safety_pool_balance(env: Env) -> i128 {
-        financial_management::get_safety_pool_balance(&env)
+    pub fn create_subscription(
+        env: Env,
+        subscriber: Address,
+        policy_id: u64,
+        start_date: u64,
+    ) -> Result<u64, Error> {
+        Ok(subscription_management::create_subscription(&env, subscriber, policy_id, start_date)?)
+    }
+
+    pub fn update_subscription(
+        env: Env,
+        updater: Address,
+        subscription_id: u64,
+        new_start_date: Option<u64>,
+    ) -> Result<bool, Error> {
+        Ok(subscription_management::update_subscription(&env, subscription_id, updater, new_start_date)?)
+    }
+
+    pub fn cancel_subscription(env: Env, canceller: Address, subscription_id: u64) -> Result<bool, Error> {
+        Ok(subscription_management::cancel_subscription(&env, subscription_id, canceller)?)
     }
 
-    pub fn add_external_funding(env: Env, funder: Address, amount: i128) -> bool {
-        financial_management::add_external_funding(&env, funder, amount)
+    pub fn renew_subscription(env: Env, renewer: Address, subscription_id: u64, new_start_date: u64) -> Result<bool, Error> {
+        Ok(subscription_management::renew_subscription(&env, subscription_id, renewer, new_start_date)?)
     }
 
-    pub fn withdraw_reserve_funds(env: Env, withdrawer: Address, amount: i128, purpose: String) -> bool {
-        financial_management::withdraw_reserve_funds(&env, withdrawer, amount, purpose)
+    pub fn process_payment(env: Env, payer: Address, subscription_id: u64) -> Result<bool, Error> {
+        Ok(subscription_management::process_payment(&env, subscription_id, payer)?)
     }
 
-    pub fn conduct_financial_audit(env: Env, auditor: Address) -> bool {
-        financial_management::conduct_financial_audit(&env, auditor)
+    
+    pub fn create_claim(
+        env: Env,
+        claimer: Address,
+        subscription_id: u64,
+        amount: i128,
+        image_hash: BytesN<32>,
+        description: String,
+    ) -> Result<u64, Error> {
+        Ok(claims_processing::create_claim(&env, claimer, subscription_id, amount, image_hash, description)?)
+    }
+
+    pub fn process_claim_payout(env: Env, processor: Address, claim_id: u64) -> Result<bool, Error> {
+        Ok(claims_processing::process_claim_payout(&env, claim_id, processor)?)
+    }
+
+    pub fn update_claim(
+        env: Env,
+        updater: Address,
+        claim_id: u64,
+        description: Option<String>,
+        new_image_hash: Option<BytesN<32>>,
+    ) -> Result<bool, Error> {
+        Ok(claims_processing::update_claim(&env, claim_id, updater, description, new_image_hash)?)
     }
 
-    pub fn emergency_fund_freeze(env: Env, freezer: Address, reason: String) -> bool {
-        financial_management::emergency_fund_freeze(&env, freezer, reason)
+    
+    pub fn get_user(env: Env, user: Address) -> Result<User, Error> {
+        Ok(user_management::get_user(&env, user)?)
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Result<Proposal, Error> {
+        Ok(dao_governance::get_proposal(&env, proposal_id)?)
+    }
+
+    pub fn get_policy(env: Env, policy_id: u64) -> Result<Policy, Error> {
+        Ok(policy_management::get_policy(&env, policy_id)?)
+    }
+
+    pub fn get_subscription(env: Env, subscription_id: u64) -> Result<Subscription, Error> {
+        Ok(subscription_management::get_subscription(&env, subscription_id)?)
     }
 
-    pub fn emergency_fund_unfreeze(env: Env, unfreezer: Address) -> bool {
-        financial_management::emergency_fund_unfreeze(&env, unfreezer)
+    pub fn get_claim(env: Env, claim_id: u64) -> Result<Claim, Error> {
+        Ok(claims_processing::get_claim(&env, claim_id)?)
+    }
+
+    pub fn get_claim_votes(env: Env, claim_id: u64) -> Result<Vec<ClaimVote>, Error> {
+        let claim = claims_processing::get_claim(&env, claim_id)?;
+        Ok(dao_governance::get_claim_votes(&env, &claim)?)
+    }
+
+    pub fn assign_assessors(
+        env: Env,
+        council_member: Address,
+        claim_id: u64,
+        assessors: Vec<Address>,
+    ) -> Result<bool, Error> {
+        Ok(claim_assessment::assign_assessors(&env, claim_id, council_member, assessors)?)
+    }
+
+    pub fn submit_assessment(
+        env: Env,
+        assessor: Address,
+        claim_id: u64,
+        decision: bool,
+        reasoning: String,
+    ) -> Result<bool, Error> {
+        Ok(claim_assessment::submit_assessment(&env, claim_id, assessor, decision, reasoning)?)
+    }
+
+    pub fn get_claim_assessors(env: Env, claim_id: u64) -> Vec<Address> {
+        claim_assessment::get_claim_assessors(&env, claim_id)
+    }
+
+    pub fn get_claim_assessments(env: Env, claim_id: u64) -> Vec<Assessment> {
+        claim_assessment::get_claim_assessments(&env, claim_id)
+    }
+
+    pub fn get_assessment_summary(env: Env, claim_id: u64) -> AssessmentSummary {
+        claim_assessment::get_assessment_summary(&env, claim_id)
+    }
+
+    
+    pub fn get_safety_pool_balance(env: Env) -> i128 {
+        financial_management::get_safety_pool_balance(&env)
+    }
+
+    pub fn add_external_funding(env: Env, funder: Address, amount: i128) -> Result<bool, Error> {
+        Ok(financial_management::add_external_funding(&env, funder, amount)?)
+    }
+
+    pub fn get_premium_token(env: Env) -> Option<Address> {
+        financial_management::get_premium_token(&env)
+    }
+
+    pub fn get_premium_payments(env: Env, payer: Address, start: u32, limit: u32) -> Vec<Payment> {
+        financial_management::get_premium_payments(&env, payer, start, limit)
+    }
+
+    pub fn conduct_financial_audit(env: Env, auditor: Address) -> Result<bool, Error> {
+        Ok(financial_management::conduct_financial_audit(&env, auditor)?)
     }
 
     pub fn get_financial_summary(env: Env) -> (i128, i128, i128, i128) {
@@ -96,6 +376,16 @@ impl VillageMicroInsuranceContract {
     }
 
     
+    pub fn bump_records(env: Env, keys: Vec<DataKey>) -> u32 {
+        storage::bump_records(&env, keys)
+    }
+
+    // Also drains the legacy payment and paid-claim maps on every call
+    pub fn migrate_legacy_records(env: Env, keys: Vec<DataKey>) -> u32 {
+        storage::migrate_instance_records(&env, keys) + financial_management::migrate_legacy_ledgers(&env)
+    }
+
+    
     pub fn is_fund_frozen(env: Env) -> bool {
         financial_management::is_fund_frozen(&env)
     }
@@ -9,11 +9,23 @@ pub enum DataKey {
     Subscription(u64),
     Claim(u64),
     DAOProposal(u64),
-    DAOVote(u64),
+    DAOVote(u64),                  // Legacy single-vote slot; superseded by `Vote`
+    Vote(VoteKey),
+    Delegation(DelegationKey),
+    Delegators(Address),           // Members who have delegated to this address, in any scope
+    ProxyVote(VoteKey),            // A member's weight cast on their behalf by a delegate
+    Vetoes(u64),                   // Council members who have vetoed a proposal
+    Unbonding(Address),            // Stake a member has asked to withdraw
+    VoteCommit(VoteKey),           // Sealed vote on a secret ballot
+    ClaimAssessors(u64),           // Members assigned to assess a claim
+    ClaimAssessments(u64),         // Assessments recorded on a claim, in order
+    ListingSlot(u64),              // Position of a proposal in its status listing
     InvestorPool(Address),
     CreditScore(Address),
     VoteWeight(Address),
     ProposalConfig(ProposalType),
+    ListLength(ListKey),           // Number of entries in an append-only list
+    ListEntry(ListPosition),       // One entry of an append-only list
     LatestPolicyId,
     LatestSubscriptionId,
     LatestClaimId,
@@ -61,6 +73,7 @@ pub struct User {
     pub last_vote_timestamp: u64,   // For vote cooldown
     pub subscribed_plan: Option<u64>, // Current active subscription
     pub village_contributions: i128, // Contributions to village
+    pub household: Option<u64>,     // Recorded by the council; used for conflict-of-interest checks
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -152,6 +165,7 @@ pub struct Claim {
     pub claim_type: ClaimType,
     pub assessor_notes: String,
     pub payout_date: Option<u64>,
+    pub proposal_id: Option<u64>,  // ClaimResolution vote opened on submission
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -192,6 +206,36 @@ pub enum ProposalStatus {
     Invalid,           // Found to be invalid
     Active,            // Active for voting
     Rejected,          // Rejected
+    Executed,          // Payload applied; terminal
+    Cancelled,         // Withdrawn by the proposer before any votes
+    Vetoed,            // Blocked by a council supermajority
+    Queued,            // Passed and waiting out its timelock
+}
+
+// Coarse status buckets used to list proposals
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ProposalListing {
+    Open,               // Open or Active
+    Passed,             // Passed, Queued or Executing
+    Executed,
+    Failed,             // Rejected, Expired, Invalid, Cancelled, Vetoed or Failed
+}
+
+impl ProposalStatus {
+    pub fn listing(&self) -> ProposalListing {
+        match self {
+            ProposalStatus::Open | ProposalStatus::Active => ProposalListing::Open,
+            ProposalStatus::Passed | ProposalStatus::Queued | ProposalStatus::Executing => ProposalListing::Passed,
+            ProposalStatus::Executed => ProposalListing::Executed,
+            ProposalStatus::Failed
+            | ProposalStatus::Rejected
+            | ProposalStatus::Expired
+            | ProposalStatus::Invalid
+            | ProposalStatus::Cancelled
+            | ProposalStatus::Vetoed => ProposalListing::Failed,
+        }
+    }
 }
 
 // Vote Weight structure
@@ -205,16 +249,34 @@ pub struct VoteWeight {
     pub last_updated: u64,         // When weights were last calculated
 }
 
+impl VoteWeight {
+    pub fn total(&self) -> u32 {
+        self.base_weight + self.reputation_boost + self.stake_boost
+    }
+}
+
 // Proposal Configuration
 #[derive(Clone)]
 #[contracttype]
 pub struct ProposalConfig {
     pub proposal_type: ProposalType,
-    pub quorum_multiplier: u32,     // Multiplier for base quorum
+    pub quorum_multiplier: u32,     // Percent of the base quorum (150 = 1.5x)
     pub min_duration: u64,          // Minimum voting duration
     pub max_duration: u64,          // Maximum voting duration
     pub min_voter_reputation: u32,  // Minimum reputation to vote
     pub approval_threshold: u32,    // Percentage needed to pass (e.g., 7500 = 75%)
+    pub timelock_delay: u64,        // Seconds a passed proposal waits in the queue; 0 executes at once
+    pub reveal_duration: u64,       // Reveal window after a secret ballot's commit phase; 0 votes openly
+    pub tally_mode: TallyMode,
+}
+
+// How vote weight is counted; see `instructions::tally`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum TallyMode {
+    Linear,                        // Full vote weight
+    Quadratic,                     // Integer square root of the full vote weight
+    Conviction,                    // Weight grows with how long before the close a vote was cast
 }
 
 // Enhanced Proposal structure
@@ -237,8 +299,99 @@ pub struct Proposal {
     pub voting_period_end: u64,    // When voting ends
     pub votes_for: u32,            // Alternative vote counting
     pub votes_against: u32,        // Alternative vote counting
+    pub delegated_for: u32,        // Part of votes_for cast by delegates; can still be overridden
+    pub delegated_against: u32,    // Part of votes_against cast by delegates
     pub quorum_required: i128,     // Required quorum
     pub created_date: u64,         // Creation timestamp
+    pub approval_threshold: u32,   // Yes share needed to pass, in basis points (from ProposalConfig)
+    pub executed_by: Option<Address>, // Member who executed the passed proposal
+    pub executed_at: Option<u64>,  // Execution timestamp
+    pub status_reason: Option<String>, // Why it was vetoed, invalidated or left to expire
+    pub eta: Option<u64>,          // Earliest execution time once queued behind a timelock
+    pub reveal_end: Option<u64>,   // Set for secret ballots: reveals close at this time
+    pub tally_mode: TallyMode,     // Fixed from the type's config at creation
+    pub claim_id: Option<u64>,     // Set for the vote a claim opens on submission
+    pub assessment: AssessmentSummary, // Assessors' findings on a claim vote; all zero otherwise
+}
+
+// Typed payload carried in `Proposal::execution_data` (XDR-encoded)
+#[derive(Clone)]
+#[contracttype]
+pub enum ProposalAction {
+    CreatePolicy(PolicyDraft),     // PolicyCreation
+    ArchivePolicy(u64),            // PolicyArchival
+    DeletePolicy(u64),             // PolicyDeletion
+    ApproveUser(Address),          // UserApproval
+    BanUser(Address),              // UserBan
+    AddCouncilMember(Address),     // MembershipChange
+    Treasury(FinancialAction),     // FinancialDecision / EmergencyAction
+    ResolveClaim(ClaimDecision),   // ClaimResolution
+    UpdateConfig(PlatformConfig),  // GovernanceUpdate
+    UpdateProposalConfig(ProposalConfig), // GovernanceUpdate
+    SlashStake(StakeSlash),        // UserBan
+    RemoveCouncilMember(Address),  // MembershipChange
+    Batch(Vec<ProposalAction>),    // Runs in order, all or nothing; see `decode_action`
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct PolicyDraft {
+    pub title: String,
+    pub description: String,
+    pub params: PolicyParams,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum FinancialAction {
+    WithdrawReserve(ReserveWithdrawal),
+    SetMinimumReserve(i128),
+    SetReserveRatio(u64),          // Basis points
+    SetPremiumToken(Address),
+    FreezeFunds(String),           // Reason
+    UnfreezeFunds,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct ReserveWithdrawal {
+    pub recipient: Address,
+    pub amount: i128,
+    pub purpose: String,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct ClaimDecision {
+    pub claim_id: u64,
+    pub approved: bool,
+    pub notes: String,
+}
+
+// Append-only lists stored as a length plus one entry per position
+#[derive(Clone)]
+#[contracttype]
+pub enum ListKey {
+    Payments(Address),             // Premiums paid by a member, oldest first
+    ProposalsByType(ProposalType), // Proposal ids, oldest first
+    ProposalsByStatus(ProposalListing), // Proposal ids, in no fixed order once proposals move
+    ProposalsByProposer(Address),  // Proposal ids, oldest first
+    MemberVotes(Address),          // Ids of proposals a member has voted on, oldest first
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct ListPosition {
+    pub list: ListKey,
+    pub position: u32,
+}
+
+// Key for a single member's vote on a proposal
+#[derive(Clone)]
+#[contracttype]
+pub struct VoteKey {
+    pub proposal_id: u64,
+    pub voter: Address,
 }
 
 // Vote Record
@@ -251,6 +404,79 @@ pub struct VoteRecord {
     pub vote: bool,                // true for yes, false for no
     pub timestamp: u64,
     pub metadata: Option<String>,  // Optional vote comment/reason
+    pub delegated_weight: u32,     // Weight carried for delegators who have not voted themselves
+}
+
+// A delegation covers every proposal type or just one; a delegation for a single
+// type takes precedence over the `All` delegation for that type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum DelegationScope {
+    All,
+    Only(ProposalType),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct DelegationKey {
+    pub delegator: Address,
+    pub scope: DelegationScope,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct VoteDelegation {
+    pub delegator: Address,
+    pub delegate: Address,
+    pub scope: DelegationScope,
+    pub created_at: u64,
+}
+
+// A member's sealed vote: sha256 of the vote byte (1 = for, 0 = against) followed by a 32-byte salt
+#[derive(Clone)]
+#[contracttype]
+pub struct VoteCommit {
+    pub proposal_id: u64,
+    pub voter: Address,
+    pub commitment: BytesN<32>,
+    pub weight: VoteWeight,        // Fixed when the commitment is made
+    pub committed_at: u64,
+}
+
+// One page of a proposal listing
+#[derive(Clone)]
+#[contracttype]
+pub struct ProposalPage {
+    pub proposals: Vec<Proposal>,
+    pub total: u32,                // Entries in the whole listing
+}
+
+// Stake on its way out; it no longer counts towards vote weight but can still be slashed
+#[derive(Clone)]
+#[contracttype]
+pub struct UnbondingStake {
+    pub member: Address,
+    pub amount: i128,
+    pub release_at: u64,           // Withdrawable from this time
+}
+
+// Slashing payload for a `UserBan` proposal
+#[derive(Clone)]
+#[contracttype]
+pub struct StakeSlash {
+    pub member: Address,
+    pub amount: i128,
+}
+
+// Weight a delegate cast for a member who did not vote directly
+#[derive(Clone)]
+#[contracttype]
+pub struct ProxyVote {
+    pub proposal_id: u64,
+    pub member: Address,
+    pub representative: Address,
+    pub weight: u32,
+    pub vote: bool,
 }
 
 // DAO Vote structure
@@ -286,6 +512,17 @@ pub struct Assessment {
     pub weight: i128,
 }
 
+// Aggregate of the assessments recorded on a claim
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct AssessmentSummary {
+    pub assessors: u32,            // Assigned
+    pub approvals: u32,
+    pub rejections: u32,
+    pub approve_weight: i128,
+    pub reject_weight: i128,
+}
+
 // Payment structure
 #[derive(Clone)]
 #[contracttype]
@@ -355,5 +592,136 @@ pub struct CreditScoreChange {
     pub source: String,
 }
 
+// Record layouts written by contract versions before households, claim votes and proposal
+// execution existed. `migrate_legacy_records` decodes these and converts them.
+#[derive(Clone)]
+#[contracttype]
+pub struct LegacyUser {
+    pub address: Address,
+    pub name: String,
+    pub credit_score: u32,
+    pub status: UserStatus,
+    pub join_date: u64,
+    pub is_dao_member: bool,
+    pub reputation_score: u32,
+    pub staked_amount: i128,
+    pub last_vote_timestamp: u64,
+    pub subscribed_plan: Option<u64>,
+    pub village_contributions: i128,
+}
+
+impl From<LegacyUser> for User {
+    fn from(user: LegacyUser) -> Self {
+        User {
+            address: user.address,
+            name: user.name,
+            credit_score: user.credit_score,
+            status: user.status,
+            join_date: user.join_date,
+            is_dao_member: user.is_dao_member,
+            reputation_score: user.reputation_score,
+            staked_amount: user.staked_amount,
+            last_vote_timestamp: user.last_vote_timestamp,
+            subscribed_plan: user.subscribed_plan,
+            village_contributions: user.village_contributions,
+            household: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct LegacyClaim {
+    pub id: u64,
+    pub subscription_id: u64,
+    pub claimer: Address,
+    pub amount: i128,
+    pub image_hash: BytesN<32>,
+    pub description: String,
+    pub status: ClaimStatus,
+    pub created_at: u64,
+    pub plan_id: u64,
+    pub claim_type: ClaimType,
+    pub assessor_notes: String,
+    pub payout_date: Option<u64>,
+}
+
+impl From<LegacyClaim> for Claim {
+    fn from(claim: LegacyClaim) -> Self {
+        Claim {
+            id: claim.id,
+            subscription_id: claim.subscription_id,
+            claimer: claim.claimer,
+            amount: claim.amount,
+            image_hash: claim.image_hash,
+            description: claim.description,
+            status: claim.status,
+            created_at: claim.created_at,
+            plan_id: claim.plan_id,
+            claim_type: claim.claim_type,
+            assessor_notes: claim.assessor_notes,
+            payout_date: claim.payout_date,
+            proposal_id: None,
+        }
+    }
+}
 
+#[derive(Clone)]
+#[contracttype]
+pub struct LegacyProposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub proposal_type: ProposalType,
+    pub title: String,
+    pub description: String,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub yes_votes: u32,
+    pub no_votes: u32,
+    pub status: ProposalStatus,
+    pub execution_data: Bytes,
+    pub required_quorum: u32,
+    pub voters: Vec<Address>,
+    pub voting_period_end: u64,
+    pub votes_for: u32,
+    pub votes_against: u32,
+    pub quorum_required: i128,
+    pub created_date: u64,
+}
 
+// Legacy proposals passed on a simple majority and were voted on openly
+impl From<LegacyProposal> for Proposal {
+    fn from(proposal: LegacyProposal) -> Self {
+        Proposal {
+            id: proposal.id,
+            proposer: proposal.proposer,
+            proposal_type: proposal.proposal_type,
+            title: proposal.title,
+            description: proposal.description,
+            start_time: proposal.start_time,
+            end_time: proposal.end_time,
+            yes_votes: proposal.yes_votes,
+            no_votes: proposal.no_votes,
+            status: proposal.status,
+            execution_data: proposal.execution_data,
+            required_quorum: proposal.required_quorum,
+            voters: proposal.voters,
+            voting_period_end: proposal.voting_period_end,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            delegated_for: 0,
+            delegated_against: 0,
+            quorum_required: proposal.quorum_required,
+            created_date: proposal.created_date,
+            approval_threshold: 5000,
+            executed_by: None,
+            executed_at: None,
+            status_reason: None,
+            eta: None,
+            reveal_end: None,
+            tally_mode: TallyMode::Linear,
+            claim_id: None,
+            assessment: AssessmentSummary::default(),
+        }
+    }
+}
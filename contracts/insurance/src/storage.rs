@@ -0,0 +1,399 @@
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val, Vec};
+use crate::constant::{
+    INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD, LIVE_RECORD_BUMP_AMOUNT,
+    LIVE_RECORD_LIFETIME_THRESHOLD, ARCHIVED_RECORD_BUMP_AMOUNT, ARCHIVED_RECORD_LIFETIME_THRESHOLD,
+};
+use crate::state::{
+    DataKey, User, UserStatus, Policy, PolicyStatus, Subscription, SubscriptionStatus, Claim,
+    ClaimStatus, Proposal, ProposalStatus, ProposalConfig, DAOVote, VoteKey, VoteRecord, VoteWeight,
+    DelegationKey, VoteDelegation, ProxyVote, UnbondingStake, VoteCommit, ListKey, ListPosition,
+    LegacyUser, LegacyClaim, LegacyProposal,
+};
+
+// Per-record entries kept in persistent storage. Each record knows its own key and
+// whether it is still live, which decides how long its TTL is extended for.
+pub trait Record: IntoVal<Env, Val> + TryFromVal<Env, Val> {
+    fn key(&self) -> DataKey;
+    fn is_live(&self) -> bool;
+}
+
+impl Record for User {
+    fn key(&self) -> DataKey {
+        DataKey::User(self.address.clone())
+    }
+
+    fn is_live(&self) -> bool {
+        self.status != UserStatus::Banned
+    }
+}
+
+impl Record for Policy {
+    fn key(&self) -> DataKey {
+        DataKey::Policy(self.id)
+    }
+
+    fn is_live(&self) -> bool {
+        matches!(self.status, PolicyStatus::Pending | PolicyStatus::Active)
+    }
+}
+
+impl Record for Subscription {
+    fn key(&self) -> DataKey {
+        DataKey::Subscription(self.id)
+    }
+
+    fn is_live(&self) -> bool {
+        matches!(self.status, SubscriptionStatus::Active | SubscriptionStatus::GracePeriod)
+    }
+}
+
+impl Record for Claim {
+    fn key(&self) -> DataKey {
+        DataKey::Claim(self.id)
+    }
+
+    fn is_live(&self) -> bool {
+        !matches!(self.status, ClaimStatus::Paid | ClaimStatus::Rejected)
+    }
+}
+
+impl Record for Proposal {
+    fn key(&self) -> DataKey {
+        DataKey::DAOProposal(self.id)
+    }
+
+    fn is_live(&self) -> bool {
+        matches!(
+            self.status,
+            ProposalStatus::Open | ProposalStatus::Passed | ProposalStatus::Queued | ProposalStatus::Executing
+        )
+    }
+}
+
+impl Record for ProposalConfig {
+    fn key(&self) -> DataKey {
+        DataKey::ProposalConfig(self.proposal_type.clone())
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
+}
+
+impl Record for DAOVote {
+    fn key(&self) -> DataKey {
+        DataKey::DAOVote(self.proposal_id)
+    }
+
+    fn is_live(&self) -> bool {
+        false
+    }
+}
+
+impl Record for VoteRecord {
+    fn key(&self) -> DataKey {
+        DataKey::Vote(VoteKey { proposal_id: self.proposal_id, voter: self.voter.clone() })
+    }
+
+    fn is_live(&self) -> bool {
+        false
+    }
+}
+
+impl Record for VoteWeight {
+    fn key(&self) -> DataKey {
+        DataKey::VoteWeight(self.voter.clone())
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
+}
+
+impl Record for VoteDelegation {
+    fn key(&self) -> DataKey {
+        DataKey::Delegation(DelegationKey {
+            delegator: self.delegator.clone(),
+            scope: self.scope.clone(),
+        })
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
+}
+
+impl Record for ProxyVote {
+    fn key(&self) -> DataKey {
+        DataKey::ProxyVote(VoteKey { proposal_id: self.proposal_id, voter: self.member.clone() })
+    }
+
+    fn is_live(&self) -> bool {
+        false
+    }
+}
+
+impl Record for UnbondingStake {
+    fn key(&self) -> DataKey {
+        DataKey::Unbonding(self.member.clone())
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
+}
+
+impl Record for VoteCommit {
+    fn key(&self) -> DataKey {
+        DataKey::VoteCommit(VoteKey { proposal_id: self.proposal_id, voter: self.voter.clone() })
+    }
+
+    fn is_live(&self) -> bool {
+        false
+    }
+}
+
+// Allocates the next id from one of the `Latest*Id` counters.
+// Ids start at 1 and are never reused, so records created in the same ledger cannot collide.
+pub fn next_id(env: &Env, counter: DataKey) -> u64 {
+    let id = env.storage().instance().get::<_, u64>(&counter).unwrap_or(0) + 1;
+    env.storage().instance().set(&counter, &id);
+    id
+}
+
+pub fn load<R: Record>(env: &Env, key: &DataKey) -> Option<R> {
+    env.storage().persistent().get(key)
+}
+
+pub fn has(env: &Env, key: &DataKey) -> bool {
+    env.storage().persistent().has(key)
+}
+
+pub fn save<R: Record>(env: &Env, record: &R) {
+    let key = record.key();
+    env.storage().persistent().set(&key, record);
+    extend_record(env, &key, record.is_live());
+    extend_instance(env);
+}
+
+pub fn remove(env: &Env, key: &DataKey) {
+    env.storage().persistent().remove(key);
+}
+
+// Indexes are short lists of record ids (or addresses) kept beside the records they point to
+// and loaded whole; anything that can grow without bound is kept as a list instead.
+pub fn load_index<T>(env: &Env, key: &DataKey) -> Vec<T>
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    env.storage().persistent().get(key).unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn push_index<T>(env: &Env, key: &DataKey, id: T)
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let mut ids = load_index::<T>(env, key);
+    ids.push_back(id);
+    save_index(env, key, &ids);
+}
+
+pub fn save_index<T>(env: &Env, key: &DataKey, ids: &Vec<T>)
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    if ids.is_empty() {
+        env.storage().persistent().remove(key);
+    } else {
+        env.storage().persistent().set(key, ids);
+        extend_record(env, key, true);
+    }
+}
+
+// Lists are kept as a length plus one entry per position, so appending to one or reading
+// a page of it never loads the whole list.
+pub fn list_len(env: &Env, list: &ListKey) -> u32 {
+    env.storage().persistent().get(&DataKey::ListLength(list.clone())).unwrap_or(0)
+}
+
+pub fn list_push<T>(env: &Env, list: &ListKey, item: &T) -> u32
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let position = list_len(env, list);
+    let entry = DataKey::ListEntry(ListPosition { list: list.clone(), position });
+    env.storage().persistent().set(&entry, item);
+    extend_record(env, &entry, true);
+
+    let length = DataKey::ListLength(list.clone());
+    env.storage().persistent().set(&length, &(position + 1));
+    extend_record(env, &length, true);
+    position
+}
+
+pub fn list_get<T>(env: &Env, list: &ListKey, position: u32) -> Option<T>
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    env.storage().persistent().get(&DataKey::ListEntry(ListPosition { list: list.clone(), position }))
+}
+
+// Up to `limit` entries from `start`, in list order
+pub fn list_page<T>(env: &Env, list: &ListKey, start: u32, limit: u32) -> Vec<T>
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let end = start.saturating_add(limit).min(list_len(env, list));
+    let mut items = Vec::new(env);
+    for position in start..end {
+        if let Some(item) = list_get(env, list, position) {
+            items.push_back(item);
+        }
+    }
+    items
+}
+
+// Removes the entry at `position` by moving the last entry into its place. Returns the
+// moved entry, if any, so the caller can record where it now sits.
+pub fn list_swap_remove<T>(env: &Env, list: &ListKey, position: u32) -> Option<T>
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let length = list_len(env, list);
+    if position >= length {
+        return None;
+    }
+    let last = length - 1;
+    let last_entry = DataKey::ListEntry(ListPosition { list: list.clone(), position: last });
+    let moved = if position < last {
+        let item: Option<T> = env.storage().persistent().get(&last_entry);
+        if let Some(item) = &item {
+            let entry = DataKey::ListEntry(ListPosition { list: list.clone(), position });
+            env.storage().persistent().set(&entry, item);
+            extend_record(env, &entry, true);
+        }
+        item
+    } else {
+        None
+    };
+    env.storage().persistent().remove(&last_entry);
+
+    let length_key = DataKey::ListLength(list.clone());
+    if last == 0 {
+        env.storage().persistent().remove(&length_key);
+    } else {
+        env.storage().persistent().set(&length_key, &last);
+        extend_record(env, &length_key, true);
+    }
+    moved
+}
+
+// Remembers where an entry sits in a list that entries are removed from
+pub fn list_slot(env: &Env, key: &DataKey) -> Option<u32> {
+    env.storage().persistent().get(key)
+}
+
+pub fn set_list_slot(env: &Env, key: &DataKey, position: u32) {
+    env.storage().persistent().set(key, &position);
+    extend_record(env, key, true);
+}
+
+fn bump_index(env: &Env, key: &DataKey) -> bool {
+    if env.storage().persistent().has(key) {
+        extend_record(env, key, true);
+        true
+    } else {
+        false
+    }
+}
+
+pub fn extend_instance(env: &Env) {
+    env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_record(env: &Env, key: &DataKey, live: bool) {
+    if live {
+        env.storage().persistent().extend_ttl(key, LIVE_RECORD_LIFETIME_THRESHOLD, LIVE_RECORD_BUMP_AMOUNT);
+    } else {
+        env.storage().persistent().extend_ttl(key, ARCHIVED_RECORD_LIFETIME_THRESHOLD, ARCHIVED_RECORD_BUMP_AMOUNT);
+    }
+}
+
+fn bump<R: Record>(env: &Env, key: &DataKey) -> bool {
+    match load::<R>(env, key) {
+        Some(record) => {
+            extend_record(env, key, record.is_live());
+            true
+        }
+        None => false,
+    }
+}
+
+// Extends the TTL of each listed record according to its current status.
+// Returns how many of the keys referred to an existing record.
+pub fn bump_records(env: &Env, keys: Vec<DataKey>) -> u32 {
+    extend_instance(env);
+    let mut bumped = 0;
+    for key in keys.iter() {
+        let found = match key {
+            DataKey::User(_) => bump::<User>(env, &key),
+            DataKey::Policy(_) => bump::<Policy>(env, &key),
+            DataKey::Subscription(_) => bump::<Subscription>(env, &key),
+            DataKey::Claim(_) => bump::<Claim>(env, &key),
+            DataKey::DAOProposal(_) => bump::<Proposal>(env, &key),
+            DataKey::ProposalConfig(_) => bump::<ProposalConfig>(env, &key),
+            DataKey::DAOVote(_) => bump::<DAOVote>(env, &key),
+            DataKey::Vote(_) => bump::<VoteRecord>(env, &key),
+            DataKey::VoteWeight(_) => bump::<VoteWeight>(env, &key),
+            DataKey::Delegators(_)
+            | DataKey::Vetoes(_)
+            | DataKey::ListingSlot(_)
+            | DataKey::ClaimAssessors(_)
+            | DataKey::ClaimAssessments(_)
+            | DataKey::ListLength(_)
+            | DataKey::ListEntry(_) => bump_index(env, &key),
+            DataKey::Delegation(_) => bump::<VoteDelegation>(env, &key),
+            DataKey::ProxyVote(_) => bump::<ProxyVote>(env, &key),
+            DataKey::Unbonding(_) => bump::<UnbondingStake>(env, &key),
+            DataKey::VoteCommit(_) => bump::<VoteCommit>(env, &key),
+            _ => false,
+        };
+        if found {
+            bumped += 1;
+        }
+    }
+    bumped
+}
+
+// Reads a record in its legacy layout `L` and saves it converted to the current one
+fn migrate<L: TryFromVal<Env, Val>, R: Record + From<L>>(env: &Env, key: &DataKey) -> bool {
+    match env.storage().instance().get::<_, L>(key) {
+        Some(legacy) => {
+            env.storage().instance().remove(key);
+            save(env, &R::from(legacy));
+            true
+        }
+        None => false,
+    }
+}
+
+// Moves records written to instance storage by earlier contract versions into persistent storage.
+// Safe to call repeatedly; keys with nothing left in instance storage are skipped.
+pub fn migrate_instance_records(env: &Env, keys: Vec<DataKey>) -> u32 {
+    let mut migrated = 0;
+    for key in keys.iter() {
+        let moved = match key {
+            DataKey::User(_) => migrate::<LegacyUser, User>(env, &key),
+            DataKey::Policy(_) => migrate::<Policy, Policy>(env, &key),
+            DataKey::Subscription(_) => migrate::<Subscription, Subscription>(env, &key),
+            DataKey::Claim(_) => migrate::<LegacyClaim, Claim>(env, &key),
+            DataKey::DAOProposal(_) => migrate::<LegacyProposal, Proposal>(env, &key),
+            DataKey::DAOVote(_) => migrate::<DAOVote, DAOVote>(env, &key),
+            _ => false,
+        };
+        if moved {
+            migrated += 1;
+        }
+    }
+    migrated
+}
@@ -1,21 +1,2334 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{vec, Env, String};
+use crate::constant::{
+    CLAIMS, PREMIUM_PAID, PREMIUM_PAYMENTS, DAY_IN_LEDGERS, LIVE_RECORD_BUMP_AMOUNT, ARCHIVED_RECORD_BUMP_AMOUNT,
+};
+use soroban_sdk::testutils::storage::Persistent as _;
+use soroban_sdk::testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{symbol_short, vec, BytesN, Env, IntoVal, Map, String, Vec};
+
+// Deploys with a one-member bootstrap council, a fresh premium token and the default
+// platform config and reserve settings
+fn setup(env: &Env) -> VillageMicroInsuranceContractClient<'_> {
+    setup_with_council(env, &vec![env, Address::generate(env)])
+}
+
+// Like `setup`, with `council` as the whole bootstrap council
+fn setup_with_council<'a>(env: &Env, council: &Vec<Address>) -> VillageMicroInsuranceContractClient<'a> {
+    let admin = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env)).address();
+    let config = PlatformConfig { max_claim_amount_ratio: 80, ..platform_config() };
+    deploy(env, &admin, &token, council, &config, 10_000, 7000)
+}
+
+fn deploy<'a>(
+    env: &Env,
+    admin: &Address,
+    token: &Address,
+    council: &Vec<Address>,
+    config: &PlatformConfig,
+    minimum_reserve: i128,
+    reserve_ratio: u64,
+) -> VillageMicroInsuranceContractClient<'a> {
+    let contract_id = env.register(
+        VillageMicroInsuranceContract,
+        (admin, token, council.clone(), config.clone(), minimum_reserve, reserve_ratio),
+    );
+    VillageMicroInsuranceContractClient::new(env, &contract_id)
+}
+
+fn make_council_member(env: &Env, client: &VillageMicroInsuranceContractClient, member: &Address) {
+    client.register_user(member, &None);
+    env.as_contract(&client.address, || {
+        user_management::enroll_council_member(env, member);
+    });
+}
+
+// Applies a treasury action as an executed proposal would
+fn treasury_action(env: &Env, client: &VillageMicroInsuranceContractClient, council: &Address, action: FinancialAction) {
+    env.as_contract(&client.address, || {
+        financial_management::FinancialManagementService::execute_financial_action(env, council.clone(), action).unwrap();
+    });
+}
+
+// Registers a Stellar Asset Contract and configures it as the premium token, as an
+// executed treasury proposal would
+fn setup_token(env: &Env, client: &VillageMicroInsuranceContractClient, council: &Address) -> Address {
+    let issuer = Address::generate(env);
+    let token = env.register_stellar_asset_contract_v2(issuer).address();
+    treasury_action(env, client, council, FinancialAction::SetPremiumToken(token.clone()));
+    token
+}
+
+fn policy_params(env: &Env) -> PolicyParams {
+    PolicyParams {
+        max_claim_amount: 50_000_000_000,
+        interest_rate: 500,
+        premium_amount: 200_000_000,
+        premium_currency: String::from_str(env, "XLM"),
+        claim_cooldown_days: 14,
+        investor_lock_in_days: 30,
+        requires_dao_approval: true,
+        credit_slash_on_reject: 10,
+    }
+}
+
+fn create_policy(env: &Env, client: &VillageMicroInsuranceContractClient, creator: &Address) -> u64 {
+    client.create_policy(
+        creator,
+        &String::from_str(env, "Crop Loss - Monsoon"),
+        &String::from_str(env, "Cover for failed monsoon crop"),
+        &policy_params(env),
+    )
+}
+
+#[test]
+fn test_policy_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creator = Address::generate(&env);
+    client.register_user(&creator, &None);
+
+    let policy_id = create_policy(&env, &client, &creator);
+    let policy = client.get_policy(&policy_id);
+    assert_eq!(policy.status, PolicyStatus::Active);
+    assert_eq!(policy.creator, creator);
+    assert!(client.validate_policy_eligibility(&creator, &policy_id));
+
+    client.update_policy(
+        &creator,
+        &policy_id,
+        &Some(String::from_str(&env, "Crop Loss - Kharif")),
+        &None,
+        &None,
+    );
+    assert_eq!(client.get_policy(&policy_id).title, String::from_str(&env, "Crop Loss - Kharif"));
+
+    client.archive_policy(&creator, &policy_id);
+    assert_eq!(client.get_policy(&policy_id).status, PolicyStatus::Archived);
+    assert_eq!(
+        client.try_validate_policy_eligibility(&creator, &policy_id),
+        Err(Ok(Error::PolicyNotActive))
+    );
+
+    client.delete_policy(&creator, &policy_id);
+    assert_eq!(client.try_get_policy(&policy_id).err(), Some(Ok(Error::PolicyNotFound)));
+}
+
+#[test]
+fn test_create_policy_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creator = Address::generate(&env);
+
+    let title = String::from_str(&env, "Livestock");
+    let description = String::from_str(&env, "Cover for cattle");
+    assert_eq!(
+        client.try_create_policy(&creator, &title, &description, &policy_params(&env)),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.register_user(&creator, &None);
+    let mut params = policy_params(&env);
+    params.max_claim_amount = 0;
+    assert_eq!(
+        client.try_create_policy(&creator, &title, &description, &params),
+        Err(Ok(Error::InvalidPolicyData))
+    );
+}
+
+#[test]
+fn test_delete_requires_archived_policy() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creator = Address::generate(&env);
+    client.register_user(&creator, &None);
+
+    let policy_id = create_policy(&env, &client, &creator);
+    assert_eq!(client.try_delete_policy(&creator, &policy_id), Err(Ok(Error::PolicyNotActive)));
+    assert_eq!(client.try_archive_policy(&creator, &42), Err(Ok(Error::PolicyNotFound)));
+
+    // Other registered users may not update, archive or delete someone else's policy
+    let villager = Address::generate(&env);
+    client.register_user(&villager, &None);
+    let mut params = policy_params(&env);
+    params.max_claim_amount *= 100;
+    assert_eq!(
+        client.try_update_policy(&villager, &policy_id, &None, &None, &Some(params)),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(client.get_policy(&policy_id).params.max_claim_amount, 50_000_000_000);
+    assert_eq!(client.try_archive_policy(&villager, &policy_id), Err(Ok(Error::Unauthorized)));
+    client.archive_policy(&creator, &policy_id);
+    assert_eq!(client.try_delete_policy(&villager, &policy_id), Err(Ok(Error::Unauthorized)));
+
+    // The council can still retire it
+    let council = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    client.delete_policy(&council, &policy_id);
+}
+
+#[test]
+fn test_subscribe_pay_claim_payout() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creator = Address::generate(&env);
+    let villager = Address::generate(&env);
+    make_council_member(&env, &client, &creator);
+    client.register_user(&villager, &Some(String::from_str(&env, "Asha")));
+    let token = setup_token(&env, &client, &creator);
+    StellarAssetClient::new(&env, &token).mint(&villager, &1_000_000_000);
+
+    let policy_id = create_policy(&env, &client, &creator);
+    let subscription_id = client.create_subscription(&villager, &policy_id, &0);
+    let subscription = client.get_subscription(&subscription_id);
+    assert_eq!(subscription.status, SubscriptionStatus::Active);
+    assert_eq!(subscription.subscriber, villager);
+
+    client.process_payment(&villager, &subscription_id);
+    let subscription = client.get_subscription(&subscription_id);
+    assert_eq!(subscription.weeks_paid, 1);
+    assert_eq!(subscription.total_premiums_paid, 200_000_000);
+
+    let claim_id = client.create_claim(
+        &villager,
+        &subscription_id,
+        &150_000_000,
+        &BytesN::from_array(&env, &[7; 32]),
+        &String::from_str(&env, "Flooded paddy field"),
+    );
+    // Changing the evidence starts the vote over, dropping votes cast on the old version
+    let first_vote = client.get_claim(&claim_id).proposal_id.unwrap();
+    client.vote_on_proposal(&first_vote, &creator, &false, &None);
+    client.update_claim(
+        &villager,
+        &claim_id,
+        &Some(String::from_str(&env, "Flooded paddy field, 2 acres")),
+        &Some(BytesN::from_array(&env, &[8; 32])),
+    );
+    let claim = client.get_claim(&claim_id);
+    assert_eq!(claim.status, ClaimStatus::Submitted);
+    assert_eq!(claim.image_hash, BytesN::from_array(&env, &[8; 32]));
+    assert_eq!(client.get_proposal(&first_vote).status, ProposalStatus::Cancelled);
+    let second_vote = client.get_proposal(&claim.proposal_id.unwrap());
+    assert_eq!((second_vote.claim_id, second_vote.votes_against), (Some(claim_id), 0));
+    assert_eq!(second_vote.description, claim.description);
+
+    assert_eq!(
+        client.try_process_claim_payout(&creator, &claim_id),
+        Err(Ok(Error::ClaimAlreadyProcessed))
+    );
+    pass_vote(&env, &client, client.get_claim(&claim_id).proposal_id.unwrap());
+    assert_eq!(client.get_claim(&claim_id).status, ClaimStatus::Approved);
+    client.process_claim_payout(&creator, &claim_id);
+    let claim = client.get_claim(&claim_id);
+    assert_eq!(claim.status, ClaimStatus::Paid);
+    assert!(claim.payout_date.is_some());
+    assert_eq!(TokenClient::new(&env, &token).balance(&villager), 950_000_000);
+    assert_eq!(client.get_safety_pool_balance(), 50_000_000);
+}
+
+#[test]
+fn test_subscription_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creator = Address::generate(&env);
+    let villager = Address::generate(&env);
+    client.register_user(&creator, &None);
+    client.register_user(&villager, &None);
+
+    assert_eq!(
+        client.try_create_subscription(&villager, &7, &0),
+        Err(Ok(Error::PolicyNotFound))
+    );
+    let policy_id = create_policy(&env, &client, &creator);
+
+    let subscription_id = client.create_subscription(&villager, &policy_id, &0);
+    assert_eq!(
+        client.try_process_payment(&creator, &subscription_id),
+        Err(Ok(Error::Unauthorized))
+    );
+    // Nobody but the subscriber may reschedule or cancel the cover
+    assert_eq!(
+        client.try_update_subscription(&creator, &subscription_id, &Some(100)),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_cancel_subscription(&creator, &subscription_id),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.cancel_subscription(&villager, &subscription_id);
+    assert_eq!(
+        client.try_renew_subscription(&villager, &subscription_id, &100),
+        Err(Ok(Error::SubscriptionNotActive))
+    );
+    assert_eq!(client.try_get_claim(&1).err(), Some(Ok(Error::ClaimNotFound)));
+}
+
+#[test]
+fn test_premium_payment_moves_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let villager = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    client.register_user(&villager, &None);
+
+    let policy_id = create_policy(&env, &client, &council);
+    let subscription_id = client.create_subscription(&villager, &policy_id, &0);
+
+    let token = setup_token(&env, &client, &council);
+    let token_client = TokenClient::new(&env, &token);
+    assert_eq!(client.try_process_payment(&villager, &subscription_id), Err(Ok(Error::InsufficientFunds)));
+    StellarAssetClient::new(&env, &token).mint(&villager, &1_000_000_000);
+
+    client.process_payment(&villager, &subscription_id);
+    let (emitter, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(emitter, client.address);
+    assert_eq!(topics, (PREMIUM_PAID, subscription_id).into_val(&env));
+    let data: (Address, i128, u64) = data.into_val(&env);
+    assert_eq!(data, (villager.clone(), 200_000_000, 1));
+    assert_eq!(token_client.balance(&villager), 800_000_000);
+    assert_eq!(token_client.balance(&client.address), 200_000_000);
+    assert_eq!(client.get_safety_pool_balance(), 200_000_000);
+
+    // Paying after the due date adds the 5% late penalty
+    env.ledger().set_timestamp(client.get_subscription(&subscription_id).next_payment_due + 1);
+    client.process_payment(&villager, &subscription_id);
+    assert_eq!(token_client.balance(&client.address), 410_000_000);
+    assert_eq!(client.get_subscription(&subscription_id).weeks_paid, 2);
+
+    let (total_premiums, _, _, _) = client.get_financial_summary();
+    assert_eq!(total_premiums, 410_000_000);
+    let history = client.get_premium_payments(&villager, &0, &10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap().week_number, 2);
+    assert_eq!(history.get(1).unwrap().penalty_applied, 10_000_000);
+    assert_eq!(client.get_premium_payments(&villager, &1, &10).len(), 1);
+}
+
+#[test]
+fn test_premium_token_changes_only_while_nothing_is_held() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    let token = setup_token(&env, &client, &council);
+
+    // Stake held in the current token, even while unbonding, blocks the swap
+    StellarAssetClient::new(&env, &token).mint(&council, &1_000_000_000);
+    client.stake(&council, &1_000_000_000);
+    let release_at = client.request_unstake(&council, &1_000_000_000);
+    // Unbonding outlasts the vote and its execution window, so let part of it run first
+    env.ledger().with_mut(|li| li.timestamp += 7 * 86_400);
+
+    let replacement = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let swap = ProposalAction::Treasury(FinancialAction::SetPremiumToken(replacement.clone()));
+    let proposal_id = passed_proposal(&env, &client, &council, ProposalType::FinancialDecision, swap);
+    assert_eq!(client.try_execute_proposal(&proposal_id, &council), Err(Ok(Error::TokenInUse)));
+    assert_eq!(client.get_premium_token(), Some(token.clone()));
+
+    env.ledger().with_mut(|li| li.timestamp = li.timestamp.max(release_at));
+    client.withdraw_stake(&council);
+    assert!(client.execute_proposal(&proposal_id, &council));
+    assert_eq!(client.get_premium_token(), Some(replacement));
+}
+
+// Subscribes a funded villager, pays one premium and files a claim for `amount` that the DAO approves
+fn approved_claim(
+    env: &Env,
+    client: &VillageMicroInsuranceContractClient,
+    council: &Address,
+    villager: &Address,
+    amount: i128,
+) -> u64 {
+    let policy_id = create_policy(env, client, council);
+    let subscription_id = client.create_subscription(villager, &policy_id, &0);
+    client.process_payment(villager, &subscription_id);
+    let claim_id = client.create_claim(
+        villager,
+        &subscription_id,
+        &amount,
+        &BytesN::from_array(env, &[1; 32]),
+        &String::from_str(env, "Cattle lost to disease"),
+    );
+    pass_vote(env, client, client.get_claim(&claim_id).proposal_id.unwrap());
+    claim_id
+}
+
+#[test]
+fn test_claim_payout_guards() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let villager = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    client.register_user(&villager, &None);
+    let token = setup_token(&env, &client, &council);
+    StellarAssetClient::new(&env, &token).mint(&villager, &1_000_000_000);
+
+    // Pool holds 200 XLM with a 10000 stroop minimum reserve, so the full premium cannot be paid out
+    let claim_id = approved_claim(&env, &client, &council, &villager, 200_000_000);
+    assert_eq!(
+        client.try_process_claim_payout(&council, &claim_id),
+        Err(Ok(Error::InsufficientReserves))
+    );
+    assert_eq!(client.get_claim(&claim_id).status, ClaimStatus::Approved);
+
+    env.as_contract(&client.address, || {
+        let mut claim: Claim = storage::load(&env, &DataKey::Claim(claim_id)).unwrap();
+        claim.amount = 100_000_000;
+        storage::save(&env, &claim);
+    });
+    treasury_action(&env, &client, &council, FinancialAction::FreezeFunds(String::from_str(&env, "Audit pending")));
+    assert_eq!(client.try_process_claim_payout(&council, &claim_id), Err(Ok(Error::FundFrozen)));
+    treasury_action(&env, &client, &council, FinancialAction::UnfreezeFunds);
+
+    client.process_claim_payout(&council, &claim_id);
+    assert_eq!(client.try_process_claim_payout(&council, &claim_id), Err(Ok(Error::ClaimAlreadyPaid)));
+    assert_eq!(TokenClient::new(&env, &token).balance(&villager), 900_000_000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&client.address), 100_000_000);
+
+    let pool = env.as_contract(&client.address, || financial_management::get_safety_pool_details(&env));
+    assert_eq!(pool.total_balance, 100_000_000);
+    assert_eq!(pool.claim_payouts, 100_000_000);
+    let (total_premiums, total_claims, net_balance, _) = client.get_financial_summary();
+    assert_eq!((total_premiums, total_claims, net_balance), (200_000_000, 100_000_000, 100_000_000));
+}
+
+#[test]
+fn test_claim_capacity_is_backed_by_held_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let villager = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    client.register_user(&villager, &None);
+    let token = setup_token(&env, &client, &council);
+    StellarAssetClient::new(&env, &token).mint(&villager, &1_000_000_000);
+    StellarAssetClient::new(&env, &token).mint(&council, &500_000_000);
+
+    // Only the subscriber may claim against a subscription
+    let policy_id = create_policy(&env, &client, &council);
+    let subscription_id = client.create_subscription(&villager, &policy_id, &0);
+    assert_eq!(
+        client.try_create_claim(
+            &council,
+            &subscription_id,
+            &100,
+            &BytesN::from_array(&env, &[1; 32]),
+            &String::from_str(&env, "Not my cattle"),
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // The pool's ledger balance is far above the claim, but the contract only holds
+    // the 20 XLM premium plus 50 XLM of stake that is not the pool's to spend
+    let claim_id = approved_claim(&env, &client, &council, &villager, 250_000_000);
+    client.add_external_funding(&council, &1_000_000_000);
+    client.stake(&council, &500_000_000);
+    assert_eq!(
+        client.try_process_claim_payout(&council, &claim_id),
+        Err(Ok(Error::InsufficientReserves))
+    );
+    assert_eq!(TokenClient::new(&env, &token).balance(&client.address), 700_000_000);
+
+    // Reserve withdrawals are held to the same tokens and pay them out to the recipient
+    let treasurer = Address::generate(&env);
+    let withdraw = |amount| {
+        let action = FinancialAction::WithdrawReserve(ReserveWithdrawal {
+            recipient: treasurer.clone(),
+            amount,
+            purpose: String::from_str(&env, "Repairs"),
+        });
+        passed_proposal(&env, &client, &council, ProposalType::FinancialDecision, ProposalAction::Treasury(action))
+    };
+    let too_much = withdraw(300_000_000);
+    assert_eq!(client.try_execute_proposal(&too_much, &council).err(), Some(Ok(Error::InsufficientReserves)));
+    let repairs = withdraw(150_000_000);
+    assert!(client.execute_proposal(&repairs, &council));
+    assert_eq!(TokenClient::new(&env, &token).balance(&treasurer), 150_000_000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&client.address), 550_000_000);
+}
+
+#[test]
+fn test_register_user_requires_user_signature() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    env.set_auths(&[]);
+    let villager = Address::generate(&env);
+    let attacker = Address::generate(&env);
+
+    assert!(client.try_register_user(&villager, &None).is_err());
+    assert!(client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "register_user",
+                args: (&villager, None::<String>).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_register_user(&villager, &None)
+        .is_err());
+    assert_eq!(client.try_get_user(&villager).err(), Some(Ok(Error::UserNotFound)));
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &villager,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "register_user",
+                args: (&villager, None::<String>).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .register_user(&villager, &None);
+    assert_eq!(client.get_user(&villager).address, villager);
+}
+
+#[test]
+fn test_spoofed_council_member_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let villager = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    client.register_user(&attacker, &None);
+    client.register_user(&villager, &None);
+
+    let reason = String::from_str(&env, "Fraudulent claims");
+    assert!(client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "propose_user_ban",
+                args: (&villager, &council, reason.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_propose_user_ban(&villager, &council, &reason)
+        .is_err());
+    assert_eq!(client.get_active_proposals(&0, &10).total, 0);
+
+    env.mock_all_auths();
+    let proposal_id = client.create_proposal(
+        &council,
+        &ProposalType::UserBan,
+        &String::from_str(&env, "Ban villager"),
+        &reason,
+        &ProposalAction::BanUser(villager.clone()).to_xdr(&env),
+        &None,
+    );
+    assert!(client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "vote_on_proposal",
+                args: (proposal_id, &council, true, None::<String>).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_vote_on_proposal(&proposal_id, &council, &true, &None)
+        .is_err());
+    assert_eq!(client.get_proposal(&proposal_id).voters.len(), 0);
+
+    // A member's own request only opens a ban proposal
+    let ban_id = client
+        .mock_auths(&[MockAuth {
+            address: &council,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "propose_user_ban",
+                args: (&villager, &council, reason.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .propose_user_ban(&villager, &council, &reason);
+    let proposal = client.get_proposal(&ban_id);
+    assert_eq!((proposal.proposal_type, proposal.description), (ProposalType::UserBan, reason));
+    assert_eq!(client.get_user(&villager).status, UserStatus::Active);
+}
+
+#[test]
+fn test_errors_surface_as_contract_codes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let villager = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    client.register_user(&villager, &None);
+
+    assert_eq!(client.try_register_user(&villager, &None), Err(Ok(Error::UserAlreadyExists)));
+    assert_eq!(
+        client.try_approve_user(&Address::generate(&env), &council),
+        Err(Ok(Error::UserNotFound))
+    );
+    assert_eq!(client.try_execute_proposal(&99, &council), Err(Ok(Error::ProposalNotFound)));
+    assert_eq!(client.try_add_external_funding(&council, &0), Err(Ok(Error::InvalidAmount)));
+    assert_eq!(client.try_conduct_financial_audit(&villager), Err(Ok(Error::Unauthorized)));
+
+    treasury_action(&env, &client, &council, FinancialAction::FreezeFunds(String::from_str(&env, "Investigation")));
+    assert_eq!(client.try_add_external_funding(&council, &100), Err(Ok(Error::FundFrozen)));
+    assert_eq!(Error::FundFrozen as u32, 603);
+}
+
+#[test]
+fn test_ids_are_unique_within_a_ledger() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let villager = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    client.register_user(&villager, &None);
+
+    let mut claim_ids = Vec::new(&env);
+    for i in 0..5_u64 {
+        let policy_id = create_policy(&env, &client, &council);
+        assert_eq!(policy_id, i + 1);
+        let subscription_id = client.create_subscription(&villager, &policy_id, &0);
+        assert_eq!(subscription_id, i + 1);
+        let claim_id = client.create_claim(
+            &villager,
+            &subscription_id,
+            &(100 + i as i128),
+            &BytesN::from_array(&env, &[i as u8; 32]),
+            &String::from_str(&env, "Storm damage"),
+        );
+        claim_ids.push_back(claim_id);
+    }
+    for (i, claim_id) in claim_ids.iter().enumerate() {
+        let claim = client.get_claim(&claim_id);
+        assert_eq!(claim.subscription_id, i as u64 + 1);
+        assert_eq!(claim.amount, 100 + i as i128);
+        // Each claim opened its own vote
+        assert_eq!(claim.proposal_id, Some(i as u64 + 1));
+    }
+
+    let title = String::from_str(&env, "Proposal");
+    let first = client.create_proposal(&council, &ProposalType::PolicyCreation, &title, &title, &sample_payload(&env, ProposalType::PolicyCreation), &None);
+    let reason = String::from_str(&env, "Repeated false claims");
+    env.as_contract(&client.address, || {
+        user_management::propose_user_ban(&env, villager.clone(), council.clone(), reason.clone()).unwrap();
+    });
+    let appointment = ProposalAction::AddCouncilMember(villager.clone()).to_xdr(&env);
+    client.create_proposal(&council, &ProposalType::MembershipChange, &title, &reason, &appointment, &None);
+    let last = client.create_proposal(&council, &ProposalType::PolicyArchival, &title, &title, &sample_payload(&env, ProposalType::PolicyArchival), &None);
+    assert_eq!((first, last), (6, 9));
+    assert_eq!(client.get_proposal(&7).proposal_type, ProposalType::UserBan);
+    assert_eq!(client.get_proposal(&8).proposal_type, ProposalType::MembershipChange);
+}
+
+fn record_ttl(env: &Env, client: &VillageMicroInsuranceContractClient, key: &DataKey) -> u32 {
+    env.as_contract(&client.address, || env.storage().persistent().get_ttl(key))
+}
+
+#[test]
+fn test_records_live_in_persistent_storage() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creator = Address::generate(&env);
+    client.register_user(&creator, &None);
+
+    let live_id = create_policy(&env, &client, &creator);
+    let archived_id = create_policy(&env, &client, &creator);
+    client.archive_policy(&creator, &archived_id);
+    let live_key = DataKey::Policy(live_id);
+    let archived_key = DataKey::Policy(archived_id);
+    assert_eq!(record_ttl(&env, &client, &live_key), LIVE_RECORD_BUMP_AMOUNT);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().instance().has(&live_key));
+    });
+
+    // A keeper bumping every 25 days keeps live records near their full lifetime,
+    // while the archived record is only ever topped up to the shorter one
+    let keys = vec![
+        &env,
+        live_key.clone(),
+        archived_key.clone(),
+        DataKey::User(creator.clone()),
+        DataKey::Claim(99),
+    ];
+    for _ in 0..4 {
+        env.ledger().with_mut(|li| li.sequence_number += 25 * DAY_IN_LEDGERS);
+        assert_eq!(client.bump_records(&keys), 3);
+    }
+    assert_eq!(record_ttl(&env, &client, &live_key), LIVE_RECORD_BUMP_AMOUNT);
+    assert_eq!(record_ttl(&env, &client, &archived_key), ARCHIVED_RECORD_BUMP_AMOUNT);
+}
+
+#[test]
+fn test_migrate_legacy_instance_records() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let villager = Address::generate(&env);
+
+    env.as_contract(&client.address, || {
+        let legacy = LegacyUser {
+            address: villager.clone(),
+            name: String::from_str(&env, "Ravi"),
+            credit_score: 100,
+            status: UserStatus::Active,
+            join_date: 0,
+            is_dao_member: false,
+            reputation_score: 50,
+            staked_amount: 0,
+            last_vote_timestamp: 0,
+            subscribed_plan: None,
+            village_contributions: 0,
+        };
+        env.storage().instance().set(&DataKey::User(villager.clone()), &legacy);
+        let title = String::from_str(&env, "Raise premiums");
+        let proposal = LegacyProposal {
+            id: 1,
+            proposer: villager.clone(),
+            proposal_type: ProposalType::GovernanceUpdate,
+            title: title.clone(),
+            description: title,
+            start_time: 0,
+            end_time: 7 * 86_400,
+            yes_votes: 1,
+            no_votes: 0,
+            status: ProposalStatus::Open,
+            execution_data: soroban_sdk::Bytes::new(&env),
+            required_quorum: 3,
+            voters: vec![&env, villager.clone()],
+            voting_period_end: 7 * 86_400,
+            votes_for: 1,
+            votes_against: 0,
+            quorum_required: 3,
+            created_date: 0,
+        };
+        env.storage().instance().set(&DataKey::DAOProposal(1), &proposal);
+
+        // Earlier versions also kept every premium payment in one instance map
+        let payment = Payment {
+            user: villager.clone(),
+            plan_id: 1,
+            amount: 200_000_000,
+            week_number: 1,
+            payment_date: 0,
+            penalty_applied: 0,
+        };
+        let mut payments: Map<Address, Vec<Payment>> = Map::new(&env);
+        payments.set(villager.clone(), vec![&env, payment.clone(), Payment { week_number: 2, ..payment }]);
+        env.storage().instance().set(&PREMIUM_PAYMENTS, &payments);
+        // and paid claims in another, in the layout from before claims had their own vote
+        let claim = LegacyClaim {
+            id: 4,
+            subscription_id: 1,
+            claimer: villager.clone(),
+            amount: 150_000_000,
+            image_hash: BytesN::from_array(&env, &[1; 32]),
+            description: String::from_str(&env, "Hail"),
+            status: ClaimStatus::Paid,
+            created_at: 0,
+            plan_id: 1,
+            claim_type: ClaimType::CropLoss,
+            assessor_notes: String::from_str(&env, ""),
+            payout_date: Some(0),
+        };
+        let mut claims: Map<u64, LegacyClaim> = Map::new(&env);
+        claims.set(4, claim);
+        env.storage().instance().set(&CLAIMS, &claims);
+    });
+    assert_eq!(client.try_get_user(&villager).err(), Some(Ok(Error::UserNotFound)));
+
+    let keys = vec![&env, DataKey::User(villager.clone()), DataKey::Policy(1), DataKey::DAOProposal(1)];
+    assert_eq!(client.migrate_legacy_records(&keys), 5);
+    let user = client.get_user(&villager);
+    assert_eq!((user.name, user.household), (String::from_str(&env, "Ravi"), None));
+    let proposal = client.get_proposal(&1);
+    assert_eq!((proposal.votes_for, proposal.approval_threshold, proposal.claim_id), (1, 5000, None));
+    let claim = client.get_claim(&4);
+    assert_eq!((claim.status, claim.proposal_id), (ClaimStatus::Paid, None));
+    let history = client.get_premium_payments(&villager, &0, &10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap().week_number, 2);
+
+    assert_eq!(client.migrate_legacy_records(&keys), 0);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().instance().has(&DataKey::User(villager.clone())));
+        assert!(!env.storage().instance().has(&PREMIUM_PAYMENTS));
+    });
+}
+
+fn platform_config() -> PlatformConfig {
+    PlatformConfig {
+        grace_period_weeks: 2,
+        minimum_quorum: 3,
+        proposal_duration_days: 7,
+        max_claim_amount_ratio: 5000,
+        penalty_rate: 500,
+        council_size: 5,
+    }
+}
+
+#[test]
+fn test_constructor_bootstraps_council() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let council = vec![&env, Address::generate(&env), Address::generate(&env)];
+
+    let client = deploy(&env, &admin, &token, &council, &platform_config(), 50_000, 8000);
+    assert_eq!(client.get_admin(), Some(admin.clone()));
+    assert_eq!(client.get_premium_token(), Some(token.clone()));
+    assert_eq!(client.get_council_members(), council);
+
+    // The same checks guard every deployment
+    let oversized = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    env.as_contract(&client.address, || {
+        assert!(matches!(
+            admin::initialize(&env, admin.clone(), token.clone(), oversized, platform_config(), 50_000, 8000),
+            Err(admin::AdminError::InvalidCouncil)
+        ));
+    });
+    env.as_contract(&client.address, || {
+        assert!(matches!(
+            admin::initialize(&env, admin.clone(), token.clone(), council.clone(), platform_config(), 50_000, 10_001),
+            Err(admin::AdminError::InvalidConfig)
+        ));
+    });
+
+    // The bootstrapped council can govern straight away
+    let member = council.get(0).unwrap();
+    assert!(client.get_user(&member).is_dao_member);
+    let policy_id = create_policy(&env, &client, &member);
+    assert_eq!(client.get_policy(&policy_id).creator, member);
+
+    env.as_contract(&client.address, || {
+        let pool = financial_management::get_safety_pool_details(&env);
+        assert_eq!(pool.minimum_reserve, 50_000);
+        assert_eq!(pool.reserve_ratio, 8000);
+    });
+}
+
+#[test]
+fn test_admin_handover_and_renounce() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let successor = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let council = vec![&env, Address::generate(&env)];
+    let client = deploy(&env, &admin, &token, &council, &platform_config(), 50_000, 8000);
+
+    // The admin appoints while bootstrapping, without being a council member
+    let villager = Address::generate(&env);
+    client.register_user(&villager, &None);
+    client.add_council_member(&villager, &admin);
+    assert!(client.get_user(&villager).is_dao_member);
+    assert_eq!(client.get_council_members().len(), 2);
+    assert_eq!(client.try_add_council_member(&villager, &admin).err(), Some(Ok(Error::UserAlreadyExists)));
+
+    // Seating a banned user would lift the ban
+    let outcast = Address::generate(&env);
+    client.register_user(&outcast, &None);
+    env.as_contract(&client.address, || {
+        let mut user: User = storage::load(&env, &DataKey::User(outcast.clone())).unwrap();
+        user.status = UserStatus::Banned;
+        storage::save(&env, &user);
+    });
+    assert_eq!(client.try_add_council_member(&outcast, &admin).err(), Some(Ok(Error::Unauthorized)));
+    assert_eq!(client.get_user(&outcast).status, UserStatus::Banned);
+
+    client.transfer_admin(&successor);
+    let auths = env.auths();
+    assert!(auths.iter().any(|(address, _)| *address == admin));
+    assert!(auths.iter().any(|(address, _)| *address == successor));
+    assert_eq!(client.get_admin(), Some(successor.clone()));
+
+    let newcomer = Address::generate(&env);
+    client.register_user(&newcomer, &None);
+    assert_eq!(client.try_add_council_member(&newcomer, &admin).err(), Some(Ok(Error::Unauthorized)));
+
+    client.renounce_admin();
+    assert_eq!(client.get_admin(), None);
+    assert_eq!(client.try_renounce_admin().err(), Some(Ok(Error::Unauthorized)));
+    assert_eq!(client.try_add_council_member(&newcomer, &successor).err(), Some(Ok(Error::Unauthorized)));
+}
+
+// Creates a proposal carrying `action`, votes it through and closes the voting window
+fn passed_proposal(
+    env: &Env,
+    client: &VillageMicroInsuranceContractClient,
+    proposer: &Address,
+    proposal_type: ProposalType,
+    action: ProposalAction,
+) -> u64 {
+    let title = String::from_str(env, "Proposal");
+    let proposal_id = client.create_proposal(proposer, &proposal_type, &title, &title, &action.to_xdr(env), &None);
+    client.vote_on_proposal(&proposal_id, proposer, &true, &None);
+    pass_vote(env, client, proposal_id);
+    proposal_id
+}
+
+// A payload that decodes for the given proposal type, for tests that never execute it.
+fn sample_payload(env: &Env, proposal_type: ProposalType) -> soroban_sdk::Bytes {
+    let action = match proposal_type {
+        ProposalType::PolicyCreation => ProposalAction::CreatePolicy(PolicyDraft {
+            title: String::from_str(env, "Policy"),
+            description: String::from_str(env, "Policy"),
+            params: policy_params(env),
+        }),
+        ProposalType::PolicyArchival => ProposalAction::ArchivePolicy(1),
+        ProposalType::PolicyDeletion => ProposalAction::DeletePolicy(1),
+        ProposalType::UserApproval => ProposalAction::ApproveUser(Address::generate(env)),
+        ProposalType::UserBan => ProposalAction::BanUser(Address::generate(env)),
+        ProposalType::MembershipChange => ProposalAction::AddCouncilMember(Address::generate(env)),
+        ProposalType::FinancialDecision | ProposalType::EmergencyAction => {
+            ProposalAction::Treasury(FinancialAction::SetMinimumReserve(1))
+        }
+        ProposalType::ClaimResolution => ProposalAction::ResolveClaim(ClaimDecision {
+            claim_id: 1,
+            approved: true,
+            notes: String::from_str(env, "Notes"),
+        }),
+        ProposalType::GovernanceUpdate => ProposalAction::UpdateConfig(platform_config()),
+    };
+    action.to_xdr(env)
+}
+
+// Adds yes votes from fresh council members until quorum, then finalizes after the window.
+// Timelocked proposals are left queued with the clock moved to their ETA; claim votes are
+// applied on the spot.
+fn pass_vote(env: &Env, client: &VillageMicroInsuranceContractClient, proposal_id: u64) {
+    while client.get_proposal(&proposal_id).votes_for < client.get_proposal(&proposal_id).required_quorum {
+        let voter = Address::generate(env);
+        make_council_member(env, client, &voter);
+        client.vote_on_proposal(&proposal_id, &voter, &true, &None);
+    }
+
+    let end = client.get_proposal(&proposal_id).voting_period_end;
+    env.ledger().with_mut(|li| li.timestamp = li.timestamp.max(end + 1));
+    match client.finalize_proposal(&proposal_id) {
+        ProposalStatus::Passed | ProposalStatus::Executed => {}
+        ProposalStatus::Queued => {
+            let eta = client.get_proposal(&proposal_id).eta.unwrap();
+            env.ledger().with_mut(|li| li.timestamp = eta);
+        }
+        status => panic!("proposal did not pass: {:?}", status),
+    }
+}
+
+#[test]
+fn test_execute_applies_policy_proposals_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let executor = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    make_council_member(&env, &client, &executor);
+
+    let draft = PolicyDraft {
+        title: String::from_str(&env, "Flood Cover"),
+        description: String::from_str(&env, "Cover for river flooding"),
+        params: policy_params(&env),
+    };
+    let proposal_id = passed_proposal(&env, &client, &council, ProposalType::PolicyCreation, ProposalAction::CreatePolicy(draft));
+    client.execute_proposal(&proposal_id, &executor);
+
+    let policy = client.get_policy(&1);
+    assert_eq!(policy.creator, council);
+    assert_eq!(policy.status, PolicyStatus::Active);
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.status, ProposalStatus::Executed);
+    assert_eq!(proposal.executed_by, Some(executor.clone()));
+    assert_eq!(proposal.executed_at, Some(env.ledger().timestamp()));
+    assert_eq!(
+        client.try_execute_proposal(&proposal_id, &executor).err(),
+        Some(Ok(Error::ProposalAlreadyExecuted))
+    );
+    assert_eq!(client.try_get_policy(&2).err(), Some(Ok(Error::PolicyNotFound)));
+
+    let archive_id = passed_proposal(&env, &client, &council, ProposalType::PolicyArchival, ProposalAction::ArchivePolicy(1));
+    client.execute_proposal(&archive_id, &executor);
+    assert_eq!(client.get_policy(&1).status, PolicyStatus::Archived);
+
+    let delete_id = passed_proposal(&env, &client, &council, ProposalType::PolicyDeletion, ProposalAction::DeletePolicy(1));
+    client.execute_proposal(&delete_id, &executor);
+    assert_eq!(client.try_get_policy(&1).err(), Some(Ok(Error::PolicyNotFound)));
+}
+
+#[test]
+fn test_execute_dispatches_user_treasury_and_governance_actions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let villager = Address::generate(&env);
+    let recruit = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    client.register_user(&villager, &None);
+    client.register_user(&recruit, &None);
+
+    let ban_id = passed_proposal(&env, &client, &council, ProposalType::UserBan, ProposalAction::BanUser(villager.clone()));
+    client.execute_proposal(&ban_id, &council);
+    assert_eq!(client.get_user(&villager).status, UserStatus::Banned);
+
+    let member_id = passed_proposal(
+        &env, &client, &council, ProposalType::MembershipChange, ProposalAction::AddCouncilMember(recruit.clone()),
+    );
+    // Voters added by the helpers have filled the default council of five
+    assert_eq!(client.try_execute_proposal(&member_id, &council).err(), Some(Ok(Error::CouncilFull)));
+    let mut roomier = platform_config();
+    roomier.council_size = 10;
+    env.as_contract(&client.address, || {
+        financial_management::FinancialManagementService::execute_config_update(&env, council.clone(), roomier).unwrap();
+    });
+    client.execute_proposal(&member_id, &council);
+    assert!(client.get_user(&recruit).is_dao_member);
+
+    let freeze = FinancialAction::FreezeFunds(String::from_str(&env, "Audit"));
+    let freeze_id = passed_proposal(&env, &client, &council, ProposalType::EmergencyAction, ProposalAction::Treasury(freeze));
+    client.execute_proposal(&freeze_id, &council);
+    assert!(client.is_fund_frozen());
+
+    // The votes above seated extra members, so the council needs room for them
+    let mut config = PlatformConfig { council_size: 50, ..platform_config() };
+    config.penalty_rate = 900;
+    let config_id = passed_proposal(&env, &client, &council, ProposalType::GovernanceUpdate, ProposalAction::UpdateConfig(config.clone()));
+    client.execute_proposal(&config_id, &council);
+    env.as_contract(&client.address, || {
+        assert_eq!(financial_management::FinancialManagementService::get_platform_config(&env).penalty_rate, 900);
+    });
+
+    // Configs that break deployment rules, shrink the council below its seats or ask for an
+    // unreachable quorum are refused
+    for broken in [
+        PlatformConfig { penalty_rate: 10_001, ..config.clone() },
+        PlatformConfig { council_size: 0, ..config.clone() },
+        PlatformConfig { council_size: platform_config().council_size, ..config.clone() },
+        PlatformConfig { minimum_quorum: 51, ..config.clone() },
+    ] {
+        let broken_id = passed_proposal(&env, &client, &council, ProposalType::GovernanceUpdate, ProposalAction::UpdateConfig(broken));
+        assert_eq!(client.try_execute_proposal(&broken_id, &council).err(), Some(Ok(Error::InvalidConfig)));
+    }
+    env.as_contract(&client.address, || {
+        assert_eq!(financial_management::FinancialManagementService::get_platform_config(&env).council_size, 50);
+    });
+
+    // A failing module call leaves the proposal executable
+    let withdraw = FinancialAction::WithdrawReserve(ReserveWithdrawal {
+        recipient: council.clone(),
+        amount: 1,
+        purpose: String::from_str(&env, "Repairs"),
+    });
+    let withdraw_id = passed_proposal(
+        &env, &client, &council, ProposalType::FinancialDecision, ProposalAction::Treasury(withdraw),
+    );
+    assert_eq!(client.try_execute_proposal(&withdraw_id, &council).err(), Some(Ok(Error::FundFrozen)));
+    assert_eq!(client.get_proposal(&withdraw_id).status, ProposalStatus::Queued);
+}
+
+#[test]
+fn test_proposals_reject_invalid_payloads() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let villager = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    client.register_user(&villager, &None);
+
+    // Payloads are checked when the proposal is created, not when it executes
+    let title = String::from_str(&env, "Garbage");
+    let mismatched = ProposalAction::BanUser(villager.clone()).to_xdr(&env);
+    let not_an_action = 42_u64.to_xdr(&env);
+    for payload in [mismatched, not_an_action, soroban_sdk::Bytes::new(&env)] {
+        assert_eq!(
+            client.try_create_proposal(&council, &ProposalType::UserApproval, &title, &title, &payload, &None).err(),
+            Some(Ok(Error::InvalidProposalPayload))
+        );
+    }
+    assert_eq!(client.get_active_proposals(&0, &10).total, 0);
+
+    // Only council members may execute
+    let approval = passed_proposal(&env, &client, &council, ProposalType::UserApproval, ProposalAction::ApproveUser(villager.clone()));
+    assert_eq!(client.try_execute_proposal(&approval, &villager).err(), Some(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_votes_are_recorded_per_voter() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    make_council_member(&env, &client, &alice);
+    make_council_member(&env, &client, &bob);
+
+    let title = String::from_str(&env, "Proposal");
+    let first = client.create_proposal(&alice, &ProposalType::UserBan, &title, &title, &sample_payload(&env, ProposalType::UserBan), &None);
+    let second = client.create_proposal(&alice, &ProposalType::UserApproval, &title, &title, &sample_payload(&env, ProposalType::UserApproval), &None);
+
+    let reason = String::from_str(&env, "Verified the field report");
+    client.vote_on_proposal(&first, &alice, &true, &Some(reason.clone()));
+    client.vote_on_proposal(&first, &bob, &false, &None);
+    client.vote_on_proposal(&second, &alice, &false, &None);
+
+    // Bob's vote no longer overwrites Alice's
+    let alice_vote = client.get_vote(&first, &alice);
+    assert!(alice_vote.vote);
+    assert_eq!(alice_vote.weight.total(), 1);
+    assert_eq!(alice_vote.metadata, Some(reason));
+    let bob_vote = client.get_vote(&first, &bob);
+    assert!(!bob_vote.vote);
+    assert_eq!(bob_vote.metadata, None);
+    assert_eq!(client.try_get_vote(&second, &bob).err(), Some(Ok(Error::VoteNotFound)));
+
+    let on_first = client.get_proposal_votes(&first, &0, &10);
+    assert_eq!(on_first.len(), 2);
+    assert_eq!(on_first.get(0).unwrap().voter, alice);
+    assert_eq!(on_first.get(1).unwrap().voter, bob);
+    let second_page = client.get_proposal_votes(&first, &1, &10);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page.get(0).unwrap().voter, bob);
+    assert_eq!(client.try_get_proposal_votes(&99, &0, &10).err(), Some(Ok(Error::ProposalNotFound)));
+
+    let by_alice = client.get_member_votes(&alice, &0, &10);
+    assert_eq!(by_alice.len(), 2);
+    assert_eq!(by_alice.get(0).unwrap().proposal_id, first);
+    assert_eq!(by_alice.get(1).unwrap().proposal_id, second);
+    assert_eq!(client.get_member_votes(&alice, &1, &1).get(0).unwrap().proposal_id, second);
+    assert_eq!(client.get_member_votes(&bob, &0, &10).len(), 1);
+
+    let keys = vec![
+        &env,
+        DataKey::Vote(VoteKey { proposal_id: first, voter: bob.clone() }),
+        DataKey::ListLength(ListKey::MemberVotes(alice.clone())),
+    ];
+    assert_eq!(client.bump_records(&keys), 2);
+}
 
 #[test]
-fn test() {
+fn test_vote_weight_is_computed_from_reputation_and_stake() {
     let env = Env::default();
-    let contract_id = env.register(Contract, ());
-    let client = ContractClient::new(&env, &contract_id);
+    env.mock_all_auths_allowing_non_root_auth();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let member = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    make_council_member(&env, &client, &member);
+
+    // Default reputation (50) and no stake leaves only the base weight
+    assert_eq!(client.get_vote_weight(&member).total(), 1);
+
+    env.as_contract(&client.address, || {
+        user_management::update_user_reputation(&env, member.clone(), 250, council.clone()).unwrap();
+    });
+    let weight = client.get_vote_weight(&member);
+    assert_eq!((weight.base_weight, weight.reputation_boost, weight.stake_boost), (1, 3, 0));
+    assert_eq!(client.bump_records(&vec![&env, DataKey::VoteWeight(member.clone())]), 1);
+
+    // Both boosts are capped, however large the inputs
+    env.as_contract(&client.address, || {
+        user_management::update_user_reputation(&env, member.clone(), 10_000, council.clone()).unwrap();
+    });
+    env.as_contract(&client.address, || {
+        let mut user: User = storage::load(&env, &DataKey::User(member.clone())).unwrap();
+        user.staked_amount = 100_000_000_000;
+        storage::save(&env, &user);
+        user_management::refresh_vote_weight(&env, &user);
+    });
+    assert_eq!(client.get_vote_weight(&member).total(), 11);
+
+    let title = String::from_str(&env, "Proposal");
+    let proposal_id = client.create_proposal(&council, &ProposalType::UserBan, &title, &title, &sample_payload(&env, ProposalType::UserBan), &None);
+    client.vote_on_proposal(&proposal_id, &member, &true, &None);
+    client.vote_on_proposal(&proposal_id, &council, &false, &None);
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!((proposal.votes_for, proposal.votes_against), (11, 1));
+    assert_eq!(client.get_vote(&proposal_id, &member).weight.stake_boost, 5);
+
+    // A negative stake can never reduce weight below the base
+    env.as_contract(&client.address, || {
+        let mut user: User = storage::load(&env, &DataKey::User(member.clone())).unwrap();
+        user.staked_amount = -1;
+        user.reputation_score = 0;
+        storage::save(&env, &user);
+        user_management::refresh_vote_weight(&env, &user);
+    });
+    assert_eq!(client.get_vote_weight(&member).total(), 1);
+}
+
+fn vote_as_new_members(env: &Env, client: &VillageMicroInsuranceContractClient, proposal_id: u64, yes: u32, no: u32) {
+    for i in 0..yes + no {
+        let voter = Address::generate(env);
+        make_council_member(env, client, &voter);
+        client.vote_on_proposal(&proposal_id, &voter, &(i < yes), &None);
+    }
+}
+
+#[test]
+fn test_proposal_rules_come_from_type_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+
+    let claims = client.get_proposal_config(&ProposalType::ClaimResolution);
+    assert_eq!((claims.min_duration, claims.approval_threshold), (3 * 86_400, 6000));
+    let policies = client.get_proposal_config(&ProposalType::PolicyCreation);
+    assert_eq!((policies.min_duration, policies.approval_threshold), (7 * 86_400, 5000));
+
+    let title = String::from_str(&env, "Proposal");
+    let claim_vote = client.create_proposal(&council, &ProposalType::ClaimResolution, &title, &title, &sample_payload(&env, ProposalType::ClaimResolution), &None);
+    let proposal = client.get_proposal(&claim_vote);
+    assert_eq!(proposal.voting_period_end - proposal.start_time, 3 * 86_400);
+    assert_eq!((proposal.required_quorum, proposal.approval_threshold), (3, 6000));
+    let treasury = client.create_proposal(&council, &ProposalType::FinancialDecision, &title, &title, &sample_payload(&env, ProposalType::FinancialDecision), &None);
+    assert_eq!(client.get_proposal(&treasury).required_quorum, 4);
+    assert_eq!(
+        client.try_create_proposal(&council, &ProposalType::ClaimResolution, &title, &title, &sample_payload(&env, ProposalType::ClaimResolution), &Some(7 * 86_400)).err(),
+        Some(Ok(Error::InvalidVotingPeriod))
+    );
+    let policy_vote = client.create_proposal(&council, &ProposalType::PolicyArchival, &title, &title, &sample_payload(&env, ProposalType::PolicyArchival), &Some(10 * 86_400));
+
+    // The same 3-2 split clears a 50% bar but not a 60% one
+    vote_as_new_members(&env, &client, claim_vote, 3, 2);
+    vote_as_new_members(&env, &client, policy_vote, 3, 2);
+    env.ledger().with_mut(|li| li.timestamp += 10 * 86_400 + 1);
+    assert_eq!(client.finalize_proposal(&claim_vote), ProposalStatus::Rejected);
+    assert_eq!(client.finalize_proposal(&policy_vote), ProposalStatus::Passed);
+
+    // Voters below the type's reputation floor are turned away
+    let governance = client.create_proposal(&council, &ProposalType::GovernanceUpdate, &title, &title, &sample_payload(&env, ProposalType::GovernanceUpdate), &None);
+    env.as_contract(&client.address, || {
+        let mut user: User = storage::load(&env, &DataKey::User(council.clone())).unwrap();
+        user.reputation_score = 10;
+        storage::save(&env, &user);
+    });
+    assert_eq!(
+        client.try_vote_on_proposal(&governance, &council, &true, &None).err(),
+        Some(Ok(Error::InsufficientReputation))
+    );
+}
+
+#[test]
+fn test_default_quorums_are_reachable_by_a_full_council() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let members = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let client = setup_with_council(&env, &Vec::from_array(&env, members.clone()));
+    assert_eq!(client.get_council_members().len() as u64, platform_config().council_size);
+
+    // Every type passes without the whole council turning out, even at the strictest threshold
+    let title = String::from_str(&env, "Proposal");
+    for proposal_type in [
+        ProposalType::PolicyCreation,
+        ProposalType::UserBan,
+        ProposalType::FinancialDecision,
+        ProposalType::GovernanceUpdate,
+        ProposalType::EmergencyAction,
+    ] {
+        let proposal_id = client.create_proposal(&members[0], &proposal_type, &title, &title, &sample_payload(&env, proposal_type.clone()), &None);
+        assert!(client.get_proposal(&proposal_id).required_quorum < members.len() as u32);
+        for member in &members[..4] {
+            client.vote_on_proposal(&proposal_id, member, &true, &None);
+        }
+        let end = client.get_proposal(&proposal_id).voting_period_end;
+        env.ledger().with_mut(|li| li.timestamp = end + 1);
+        let status = client.finalize_proposal(&proposal_id);
+        assert!(matches!(status, ProposalStatus::Passed | ProposalStatus::Queued), "{:?}", proposal_type);
+    }
+}
+
+#[test]
+fn test_proposal_config_changes_only_through_governance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let council = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let client = deploy(&env, &admin, &token, &vec![&env, council.clone()], &platform_config(), 50_000, 8000);
+    assert_eq!(client.bump_records(&vec![&env, DataKey::ProposalConfig(ProposalType::UserBan)]), 1);
+
+    let mut config = client.get_proposal_config(&ProposalType::UserBan);
+    config.approval_threshold = 7500;
+    config.min_duration = 2 * 86_400;
+    let proposal_id = passed_proposal(
+        &env, &client, &council, ProposalType::GovernanceUpdate, ProposalAction::UpdateProposalConfig(config.clone()),
+    );
+    client.execute_proposal(&proposal_id, &council);
+    let updated = client.get_proposal_config(&ProposalType::UserBan);
+    assert_eq!((updated.approval_threshold, updated.min_duration), (7500, 2 * 86_400));
+
+    let title = String::from_str(&env, "Ban");
+    let ban = client.create_proposal(&council, &ProposalType::UserBan, &title, &title, &sample_payload(&env, ProposalType::UserBan), &None);
+    assert_eq!(client.get_proposal(&ban).approval_threshold, 7500);
+
+    config.approval_threshold = 10_001;
+    let invalid = passed_proposal(
+        &env, &client, &council, ProposalType::GovernanceUpdate, ProposalAction::UpdateProposalConfig(config.clone()),
+    );
+    assert_eq!(client.try_execute_proposal(&invalid, &council).err(), Some(Ok(Error::InvalidProposalConfig)));
+
+    let wrong_type = ProposalAction::UpdateProposalConfig(config).to_xdr(&env);
+    assert_eq!(
+        client.try_create_proposal(&council, &ProposalType::FinancialDecision, &title, &title, &wrong_type, &None).err(),
+        Some(Ok(Error::InvalidProposalPayload))
+    );
+}
+
+#[test]
+fn test_finalize_resolves_early_once_outcome_is_locked() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let members = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let client = setup_with_council(&env, &Vec::from_array(&env, members.clone()));
+
+    let title = String::from_str(&env, "Proposal");
+    let policy_vote = client.create_proposal(&members[0], &ProposalType::PolicyCreation, &title, &title, &sample_payload(&env, ProposalType::PolicyCreation), &None);
+    client.vote_on_proposal(&policy_vote, &members[0], &true, &None);
+    client.vote_on_proposal(&policy_vote, &members[1], &true, &None);
+    // Below quorum, nothing is settled yet
+    assert_eq!(client.try_finalize_proposal(&policy_vote).err(), Some(Ok(Error::VotingStillOpen)));
+    client.vote_on_proposal(&policy_vote, &members[2], &true, &None);
+    // 3 of 5 for: even two late "no" votes leave 60% > 50%
+    assert_eq!(client.finalize_proposal(&policy_vote), ProposalStatus::Passed);
+    assert_eq!(
+        client.try_vote_on_proposal(&policy_vote, &members[3], &false, &None).err(),
+        Some(Ok(Error::ProposalNotOpen))
+    );
+
+    let claim_vote = client.create_proposal(&members[0], &ProposalType::ClaimResolution, &title, &title, &sample_payload(&env, ProposalType::ClaimResolution), &None);
+    client.vote_on_proposal(&claim_vote, &members[0], &true, &None);
+    client.vote_on_proposal(&claim_vote, &members[1], &false, &None);
+    client.vote_on_proposal(&claim_vote, &members[2], &true, &None);
+    // 2-1 with two members left could still end 4-1 or 2-3
+    assert_eq!(client.try_finalize_proposal(&claim_vote).err(), Some(Ok(Error::VotingStillOpen)));
+    client.vote_on_proposal(&claim_vote, &members[3], &false, &None);
+    // 2-2: one remaining yes reaches only 60%, which is not above the 60% bar
+    assert_eq!(client.finalize_proposal(&claim_vote), ProposalStatus::Rejected);
+    assert_eq!(client.try_finalize_proposal(&claim_vote).err(), Some(Ok(Error::ProposalNotOpen)));
+}
+
+#[test]
+fn test_unmet_quorum_expires_and_keepers_sweep() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+
+    let title = String::from_str(&env, "Proposal");
+    let quiet = client.create_proposal(&council, &ProposalType::UserApproval, &title, &title, &sample_payload(&env, ProposalType::UserApproval), &None);
+    client.vote_on_proposal(&quiet, &council, &true, &None);
+    let untouched = client.create_proposal(&council, &ProposalType::UserApproval, &title, &title, &sample_payload(&env, ProposalType::UserApproval), &None);
+    let passing = client.create_proposal(&council, &ProposalType::UserApproval, &title, &title, &sample_payload(&env, ProposalType::UserApproval), &None);
+    vote_as_new_members(&env, &client, passing, 3, 0);
+    let still_running = client.create_proposal(&council, &ProposalType::UserApproval, &title, &title, &sample_payload(&env, ProposalType::UserApproval), &Some(7 * 86_400));
+
+    env.ledger().with_mut(|li| li.timestamp += 3 * 86_400 + 1);
+    assert_eq!(client.finalize_proposal(&quiet), ProposalStatus::Expired);
+
+    let ids = vec![&env, quiet, untouched, passing, still_running, 99];
+    assert_eq!(client.sweep_expired_proposals(&ids), 2);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("PropFin"), passing).into_val(&env));
+    let (status, votes_for, votes_against): (ProposalStatus, u32, u32) = data.into_val(&env);
+    assert_eq!((status, votes_for, votes_against), (ProposalStatus::Passed, 3, 0));
+    assert_eq!(client.get_proposal(&untouched).status, ProposalStatus::Expired);
+    assert_eq!(client.get_proposal(&passing).status, ProposalStatus::Passed);
+    assert_eq!(client.get_proposal(&still_running).status, ProposalStatus::Open);
+    assert_eq!(client.sweep_expired_proposals(&ids), 0);
+
+}
+
+#[test]
+fn test_delegated_weight_follows_chain_until_overridden() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let (elder, deputy, traveller) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    for member in [&elder, &deputy, &traveller] {
+        make_council_member(&env, &client, member);
+    }
+    client.delegate_vote(&deputy, &elder, &DelegationScope::All);
+    client.delegate_vote(&traveller, &deputy, &DelegationScope::All);
+
+    let title = String::from_str(&env, "Proposal");
+    let proposal_id = client.create_proposal(&elder, &ProposalType::UserBan, &title, &title, &sample_payload(&env, ProposalType::UserBan), &None);
+    client.vote_on_proposal(&proposal_id, &elder, &true, &None);
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!((proposal.votes_for, proposal.delegated_for), (3, 2));
+    assert_eq!(client.get_vote(&proposal_id, &elder).delegated_weight, 2);
+
+    // The deputy votes directly, taking back their own weight and the traveller's
+    client.vote_on_proposal(&proposal_id, &deputy, &false, &None);
+    let overridden = env.events().all().iter().any(|(_, topics, _)| {
+        topics == (symbol_short!("DelOvr"), proposal_id).into_val(&env)
+    });
+    assert!(overridden);
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!((proposal.votes_for, proposal.votes_against), (1, 2));
+    assert_eq!((proposal.delegated_for, proposal.delegated_against), (0, 1));
+    assert_eq!(client.get_vote(&proposal_id, &elder).delegated_weight, 0);
+    assert_eq!(client.get_vote(&proposal_id, &deputy).delegated_weight, 1);
+
+    client.vote_on_proposal(&proposal_id, &traveller, &true, &None);
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!((proposal.votes_for, proposal.votes_against), (2, 1));
+    assert_eq!((proposal.delegated_for, proposal.delegated_against), (0, 0));
+}
+
+#[test]
+fn test_delegation_scopes_cycles_and_revocation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let (a, b, c) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    for member in [&a, &b, &c] {
+        make_council_member(&env, &client, member);
+    }
+    let villager = Address::generate(&env);
+    client.register_user(&villager, &None);
+
+    assert_eq!(client.try_delegate_vote(&a, &a, &DelegationScope::All).err(), Some(Ok(Error::InvalidDelegation)));
+    assert_eq!(client.try_delegate_vote(&a, &villager, &DelegationScope::All).err(), Some(Ok(Error::InvalidDelegation)));
+    assert_eq!(client.try_delegate_vote(&villager, &a, &DelegationScope::All).err(), Some(Ok(Error::Unauthorized)));
+
+    client.delegate_vote(&a, &b, &DelegationScope::All);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("VoteDel"), a.clone()).into_val(&env));
+    let (delegate, scope): (Address, DelegationScope) = data.into_val(&env);
+    assert_eq!((delegate, scope), (b.clone(), DelegationScope::All));
+
+    let ban_scope = DelegationScope::Only(ProposalType::UserBan);
+    assert_eq!(client.try_delegate_vote(&b, &a, &DelegationScope::All).err(), Some(Ok(Error::DelegationCycle)));
+    assert_eq!(client.try_delegate_vote(&b, &a, &ban_scope).err(), Some(Ok(Error::DelegationCycle)));
+    // Once A routes bans to C, B may hand bans to A without forming a loop
+    client.delegate_vote(&a, &c, &ban_scope);
+    client.delegate_vote(&b, &a, &ban_scope);
+    assert_eq!(client.try_delegate_vote(&c, &b, &ban_scope).err(), Some(Ok(Error::DelegationCycle)));
+
+    let title = String::from_str(&env, "Proposal");
+    let ban = client.create_proposal(&c, &ProposalType::UserBan, &title, &title, &sample_payload(&env, ProposalType::UserBan), &None);
+    let approval = client.create_proposal(&c, &ProposalType::UserApproval, &title, &title, &sample_payload(&env, ProposalType::UserApproval), &None);
+    client.vote_on_proposal(&ban, &c, &true, &None);
+    assert_eq!(client.get_proposal(&ban).votes_for, 3);
+    client.vote_on_proposal(&approval, &b, &true, &None);
+    assert_eq!(client.get_proposal(&approval).votes_for, 2);
+
+    client.revoke_delegation(&a, &ban_scope);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("DelRev"), a.clone()).into_val(&env));
+    assert!(client.get_delegation(&a, &ban_scope).is_none());
+    assert_eq!(client.get_delegation(&a, &DelegationScope::All).unwrap().delegate, b);
+    assert_eq!(client.try_revoke_delegation(&a, &ban_scope).err(), Some(Ok(Error::DelegationNotFound)));
+}
+
+#[test]
+fn test_proposer_cancels_only_before_votes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let (proposer, member) = (Address::generate(&env), Address::generate(&env));
+    make_council_member(&env, &client, &proposer);
+    make_council_member(&env, &client, &member);
+
+    let title = String::from_str(&env, "Proposal");
+    let mistaken = client.create_proposal(&proposer, &ProposalType::UserBan, &title, &title, &sample_payload(&env, ProposalType::UserBan), &None);
+    assert_eq!(client.try_cancel_proposal(&mistaken, &member).err(), Some(Ok(Error::Unauthorized)));
+
+    client.cancel_proposal(&mistaken, &proposer);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("PropCan"), mistaken).into_val(&env));
+    assert_eq!(client.get_proposal(&mistaken).status, ProposalStatus::Cancelled);
+    assert_eq!(client.try_vote_on_proposal(&mistaken, &member, &true, &None).err(), Some(Ok(Error::ProposalNotOpen)));
+    assert_eq!(client.try_cancel_proposal(&mistaken, &proposer).err(), Some(Ok(Error::ProposalNotOpen)));
+
+    let contested = client.create_proposal(&proposer, &ProposalType::UserBan, &title, &title, &sample_payload(&env, ProposalType::UserBan), &None);
+    client.vote_on_proposal(&contested, &member, &false, &None);
+    assert_eq!(client.try_cancel_proposal(&contested, &proposer).err(), Some(Ok(Error::VotesAlreadyCast)));
+    assert_eq!(client.get_proposal(&contested).status, ProposalStatus::Open);
+}
+
+#[test]
+fn test_council_supermajority_vetoes_proposals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (a, b, c) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    let client = setup_with_council(&env, &vec![&env, a.clone(), b.clone(), c.clone()]);
+    let villager = Address::generate(&env);
+    client.register_user(&villager, &None);
+
+    let title = String::from_str(&env, "Proposal");
+    let reason = String::from_str(&env, "Targets a member in bad faith");
+    let proposal_id = client.create_proposal(&a, &ProposalType::UserBan, &title, &title, &sample_payload(&env, ProposalType::UserBan), &None);
+    assert_eq!(client.try_veto_proposal(&proposal_id, &villager, &reason).err(), Some(Ok(Error::Unauthorized)));
+
+    assert_eq!(client.veto_proposal(&proposal_id, &b, &reason), ProposalStatus::Open);
+    assert_eq!(client.try_veto_proposal(&proposal_id, &b, &reason).err(), Some(Ok(Error::AlreadyVetoed)));
+    // Two of three members is a supermajority
+    assert_eq!(client.veto_proposal(&proposal_id, &c, &reason), ProposalStatus::Vetoed);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("PropVeto"), proposal_id).into_val(&env));
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.status_reason, Some(reason.clone()));
+    assert_eq!(client.get_proposal_vetoes(&proposal_id), vec![&env, b.clone(), c.clone()]);
+    assert_eq!(client.try_veto_proposal(&proposal_id, &a, &reason).err(), Some(Ok(Error::ProposalNotOpen)));
+
+    // A passed proposal can still be blocked before it executes
+    let passed = passed_proposal(&env, &client, &a, ProposalType::UserBan, ProposalAction::BanUser(villager.clone()));
+    let council = client.get_council_members();
+    let needed = (council.len() * 2).div_ceil(3);
+    for (index, member) in council.iter().enumerate() {
+        let status = client.veto_proposal(&passed, &member, &reason);
+        assert_eq!(status == ProposalStatus::Vetoed, index as u32 + 1 >= needed);
+        if status == ProposalStatus::Vetoed {
+            break;
+        }
+    }
+    assert_eq!(client.try_execute_proposal(&passed, &a).err(), Some(Ok(Error::ProposalNotOpen)));
+    assert_eq!(client.get_user(&villager).status, UserStatus::Active);
+}
+
+#[test]
+fn test_stale_proposals_are_invalidated_at_execution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let villager = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    client.register_user(&villager, &None);
+
+    let ban_id = passed_proposal(&env, &client, &council, ProposalType::UserBan, ProposalAction::BanUser(villager.clone()));
+    let duplicate = passed_proposal(&env, &client, &council, ProposalType::UserBan, ProposalAction::BanUser(villager.clone()));
+    client.execute_proposal(&duplicate, &council);
+    assert_eq!(client.try_approve_user(&villager, &council).err(), Some(Ok(Error::Unauthorized)));
+    assert_eq!(client.get_user(&villager).status, UserStatus::Banned);
+
+    assert!(!client.execute_proposal(&ban_id, &council));
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("PropInv"), ban_id).into_val(&env));
+    let proposal = client.get_proposal(&ban_id);
+    assert_eq!(proposal.status, ProposalStatus::Invalid);
+    assert_eq!(proposal.status_reason, Some(String::from_str(&env, "user already banned")));
+    assert_eq!(proposal.executed_by, None);
+    assert_eq!(client.try_execute_proposal(&ban_id, &council).err(), Some(Ok(Error::ProposalNotOpen)));
+
+    let delete_id = passed_proposal(&env, &client, &council, ProposalType::PolicyDeletion, ProposalAction::DeletePolicy(7));
+    assert!(!client.execute_proposal(&delete_id, &council));
+    assert_eq!(
+        client.get_proposal(&delete_id).status_reason,
+        Some(String::from_str(&env, "policy already deleted"))
+    );
+}
+
+#[test]
+fn test_treasury_proposals_wait_out_a_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    let action = ProposalAction::Treasury(FinancialAction::SetMinimumReserve(50_000));
+
+    // `passed_proposal` leaves the clock at the ETA
+    let proposal_id = passed_proposal(&env, &client, &council, ProposalType::FinancialDecision, action.clone());
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.status, ProposalStatus::Queued);
+    let eta = proposal.eta.unwrap();
+    assert_eq!(eta, proposal.voting_period_end + 1 + 2 * 86_400);
+
+    env.ledger().with_mut(|li| li.timestamp = eta - 1);
+    assert_eq!(client.try_execute_proposal(&proposal_id, &council).err(), Some(Ok(Error::TimelockActive)));
+    env.ledger().with_mut(|li| li.timestamp = eta);
+    assert!(client.execute_proposal(&proposal_id, &council));
+    assert_eq!(client.get_proposal(&proposal_id).status, ProposalStatus::Executed);
+
+    // Missing the execution window lets the queued proposal lapse
+    let stale = passed_proposal(&env, &client, &council, ProposalType::FinancialDecision, action.clone());
+    env.ledger().with_mut(|li| li.timestamp += 7 * 86_400 + 1);
+    assert!(!client.execute_proposal(&stale, &council));
+    let proposal = client.get_proposal(&stale);
+    assert_eq!(proposal.status, ProposalStatus::Expired);
+    assert_eq!(proposal.status_reason, Some(String::from_str(&env, "execution window passed")));
+
+    // Queued items can still be vetoed
+    let vetoed = passed_proposal(&env, &client, &council, ProposalType::FinancialDecision, action);
+    let reason = String::from_str(&env, "Drains the pool");
+    for member in client.get_council_members().iter() {
+        if client.veto_proposal(&vetoed, &member, &reason) == ProposalStatus::Vetoed {
+            break;
+        }
+    }
+    assert_eq!(client.try_execute_proposal(&vetoed, &council).err(), Some(Ok(Error::ProposalNotOpen)));
+
+    // Emergencies skip the queue
+    let freeze = ProposalAction::Treasury(FinancialAction::FreezeFunds(String::from_str(&env, "Breach")));
+    let emergency = passed_proposal(&env, &client, &council, ProposalType::EmergencyAction, freeze);
+    assert_eq!(client.get_proposal(&emergency).status, ProposalStatus::Passed);
+    assert_eq!(client.get_proposal(&emergency).eta, None);
+}
+
+#[test]
+fn test_lone_member_cannot_move_treasury_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let members = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    let client = setup_with_council(&env, &Vec::from_array(&env, members.clone()));
+    let lone = &members[0];
+    let token = setup_token(&env, &client, lone);
+    StellarAssetClient::new(&env, &token).mint(&client.address, &1_000_000_000);
+    client.add_external_funding(lone, &1_000_000_000);
+    treasury_action(&env, &client, lone, FinancialAction::FreezeFunds(String::from_str(&env, "Breach")));
+
+    // Treasury actions have no direct entrypoint; a proposal only one member backs never executes
+    let title = String::from_str(&env, "Treasury");
+    let withdraw = FinancialAction::WithdrawReserve(ReserveWithdrawal {
+        recipient: lone.clone(),
+        amount: 1,
+        purpose: String::from_str(&env, "Drain"),
+    });
+    let actions = [
+        (ProposalType::FinancialDecision, withdraw),
+        (ProposalType::EmergencyAction, FinancialAction::UnfreezeFunds),
+    ];
+    for (proposal_type, action) in actions {
+        let payload = ProposalAction::Treasury(action).to_xdr(&env);
+        let proposal_id = client.create_proposal(lone, &proposal_type, &title, &title, &payload, &None);
+        client.vote_on_proposal(&proposal_id, lone, &true, &None);
+        assert_eq!(client.try_execute_proposal(&proposal_id, lone).err(), Some(Ok(Error::ProposalNotOpen)));
+
+        let end = client.get_proposal(&proposal_id).voting_period_end;
+        env.ledger().with_mut(|li| li.timestamp = end + 1);
+        assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Expired);
+        assert_eq!(client.try_execute_proposal(&proposal_id, lone).err(), Some(Ok(Error::ProposalNotOpen)));
+    }
+    assert!(client.is_fund_frozen());
+    assert_eq!(TokenClient::new(&env, &token).balance(lone), 0);
+}
+
+#[test]
+fn test_timelock_bypass_needs_a_higher_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+
+    let mut config = client.get_proposal_config(&ProposalType::FinancialDecision);
+    assert_eq!(config.timelock_delay, 2 * 86_400);
+    config.timelock_delay = 0;
+    let lax = passed_proposal(
+        &env, &client, &council, ProposalType::GovernanceUpdate, ProposalAction::UpdateProposalConfig(config.clone()),
+    );
+    assert_eq!(client.try_execute_proposal(&lax, &council).err(), Some(Ok(Error::InvalidProposalConfig)));
+
+    config.approval_threshold = 7500;
+    let strict = passed_proposal(
+        &env, &client, &council, ProposalType::GovernanceUpdate, ProposalAction::UpdateProposalConfig(config),
+    );
+    client.execute_proposal(&strict, &council);
+    let title = String::from_str(&env, "Top up");
+    let payload = ProposalAction::Treasury(FinancialAction::SetMinimumReserve(1)).to_xdr(&env);
+    let proposal_id = client.create_proposal(&council, &ProposalType::FinancialDecision, &title, &title, &payload, &None);
+    pass_vote(&env, &client, proposal_id);
+    assert_eq!(client.get_proposal(&proposal_id).status, ProposalStatus::Passed);
+}
+
+#[test]
+fn test_stake_unbonds_before_withdrawal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let member = Address::generate(&env);
+    make_council_member(&env, &client, &member);
+    let token = setup_token(&env, &client, &member);
+    StellarAssetClient::new(&env, &token).mint(&member, &3_000_000_000);
+    let balance = TokenClient::new(&env, &token);
+
+    assert_eq!(client.try_stake(&member, &0).err(), Some(Ok(Error::InvalidAmount)));
+    assert_eq!(client.try_stake(&member, &4_000_000_000).err(), Some(Ok(Error::InsufficientFunds)));
+    client.stake(&member, &2_000_000_000);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("StkAdd"), member.clone()).into_val(&env));
+    assert_eq!(client.get_user(&member).staked_amount, 2_000_000_000);
+    assert_eq!(client.get_vote_weight(&member).stake_boost, 2);
+    assert_eq!(balance.balance(&client.address), 2_000_000_000);
+
+    assert_eq!(client.try_request_unstake(&member, &3_000_000_000).err(), Some(Ok(Error::InsufficientStake)));
+    let release_at = client.request_unstake(&member, &1_000_000_000);
+    assert_eq!(release_at, env.ledger().timestamp() + 21 * 86_400);
+    assert_eq!(client.get_vote_weight(&member).stake_boost, 1);
+    assert_eq!(client.get_unbonding(&member).unwrap().amount, 1_000_000_000);
+
+    assert_eq!(client.try_withdraw_stake(&member).err(), Some(Ok(Error::StakeStillUnbonding)));
+    env.ledger().with_mut(|li| li.timestamp = release_at);
+    assert_eq!(client.withdraw_stake(&member), 1_000_000_000);
+    assert_eq!(balance.balance(&member), 2_000_000_000);
+    assert!(client.get_unbonding(&member).is_none());
+    assert_eq!(client.try_withdraw_stake(&member).err(), Some(Ok(Error::NothingToWithdraw)));
+}
+
+#[test]
+fn test_dao_slashes_stake_by_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let rogue = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    make_council_member(&env, &client, &rogue);
+    let token = setup_token(&env, &client, &council);
+    StellarAssetClient::new(&env, &token).mint(&rogue, &2_000_000_000);
+    client.stake(&rogue, &2_000_000_000);
+    client.request_unstake(&rogue, &500_000_000);
+
+    // Unbonding stake is no escape, even from a slash voted over the longest ban window:
+    // the slash takes active stake first, then the rest
+    let slash = ProposalAction::SlashStake(StakeSlash { member: rogue.clone(), amount: 1_800_000_000 });
+    let title = String::from_str(&env, "Slash");
+    let longest = client.get_proposal_config(&ProposalType::UserBan).max_duration;
+    let proposal_id = client.create_proposal(&council, &ProposalType::UserBan, &title, &title, &slash.to_xdr(&env), &Some(longest));
+    pass_vote(&env, &client, proposal_id);
+    assert!(client.try_withdraw_stake(&rogue).is_err());
+    let pool_before = client.get_safety_pool_balance();
+    assert!(client.execute_proposal(&proposal_id, &council));
+    let (_, topics, data) = env.events().all().iter().find(|(_, topics, _)| {
+        topics.get(0).unwrap().shallow_eq(&symbol_short!("StkSlash").to_val())
+    }).unwrap();
+    assert_eq!(topics, (symbol_short!("StkSlash"), rogue.clone()).into_val(&env));
+    let (_, slashed): (Address, i128) = data.into_val(&env);
+    assert_eq!(slashed, 1_800_000_000);
+
+    assert_eq!(client.get_user(&rogue).staked_amount, 0);
+    assert_eq!(client.get_vote_weight(&rogue).stake_boost, 0);
+    assert_eq!(client.get_unbonding(&rogue).unwrap().amount, 200_000_000);
+    assert_eq!(client.get_safety_pool_balance(), pool_before + 1_800_000_000);
+
+    env.ledger().with_mut(|li| li.timestamp += 7 * 86_400);
+    assert_eq!(client.withdraw_stake(&rogue), 200_000_000);
+    let empty = passed_proposal(
+        &env, &client, &council, ProposalType::UserBan,
+        ProposalAction::SlashStake(StakeSlash { member: rogue.clone(), amount: 1 }),
+    );
+    assert!(!client.execute_proposal(&empty, &council));
+    assert_eq!(client.get_proposal(&empty).status_reason, Some(String::from_str(&env, "no stake to slash")));
+}
+
+fn page_ids(env: &Env, page: &ProposalPage) -> Vec<u64> {
+    let mut ids = Vec::new(env);
+    for proposal in page.proposals.iter() {
+        ids.push_back(proposal.id);
+    }
+    ids
+}
+
+#[test]
+fn test_proposal_listings_page_through_indexes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+    make_council_member(&env, &client, &alice);
+    make_council_member(&env, &client, &bob);
+
+    let title = String::from_str(&env, "Proposal");
+    for index in 0..24 {
+        let (proposer, proposal_type) = if index % 3 == 0 {
+            (&bob, ProposalType::UserBan)
+        } else {
+            (&alice, ProposalType::PolicyCreation)
+        };
+        client.create_proposal(proposer, &proposal_type, &title, &title, &sample_payload(&env, proposal_type.clone()), &None);
+    }
+
+    // Pages are capped at 20 however many are asked for
+    let first = client.get_active_proposals(&0, &100);
+    assert_eq!((first.proposals.len(), first.total), (20, 24));
+    assert_eq!(page_ids(&env, &client.get_active_proposals(&20, &10)), vec![&env, 21, 22, 23, 24]);
+    assert_eq!(client.get_active_proposals(&30, &10).proposals.len(), 0);
+
+    let bans = client.get_proposals_by_type(&ProposalType::UserBan, &0, &3);
+    assert_eq!((page_ids(&env, &bans), bans.total), (vec![&env, 1, 4, 7], 8));
+    assert_eq!(page_ids(&env, &client.get_proposals_by_proposer(&bob, &6, &5)), vec![&env, 19, 22]);
+    assert_eq!(client.get_proposals_by_proposer(&alice, &0, &1).total, 16);
+
+    // Status listings follow each transition; the newest entry fills the gap a move leaves
+    client.cancel_proposal(&2, &alice);
+    assert_eq!(page_ids(&env, &client.get_active_proposals(&0, &3)), vec![&env, 1, 24, 3]);
+    let villager = Address::generate(&env);
+    client.register_user(&villager, &None);
+    let ban = passed_proposal(&env, &client, &bob, ProposalType::UserBan, ProposalAction::BanUser(villager));
+    assert_eq!(page_ids(&env, &client.get_proposals_by_status(&ProposalListing::Passed, &0, &5)), vec![&env, ban]);
+    client.execute_proposal(&ban, &bob);
+
+    assert_eq!(page_ids(&env, &client.get_proposals_by_status(&ProposalListing::Failed, &0, &5)), vec![&env, 2]);
+    assert_eq!(page_ids(&env, &client.get_proposals_by_status(&ProposalListing::Executed, &0, &5)), vec![&env, ban]);
+    assert_eq!(client.get_proposals_by_status(&ProposalListing::Passed, &0, &5).total, 0);
+    assert_eq!(client.get_active_proposals(&0, &1).total, 23);
+}
+
+#[test]
+fn test_membership_changes_go_through_proposals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (a, b, c) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    let client = setup_with_council(&env, &vec![&env, a.clone(), b.clone(), c.clone()]);
+    let villager = Address::generate(&env);
+    client.register_user(&villager, &None);
+
+    // No single member may appoint or ban
+    assert_eq!(client.try_add_council_member(&villager, &a).err(), Some(Ok(Error::Unauthorized)));
+    let ban_c = client.propose_user_ban(&c, &a, &String::from_str(&env, "Absent for a year"));
+    assert_eq!(client.get_user(&c).status, UserStatus::Active);
+
+    // At the minimum size a ban still applies, but the seat stays, inert, until the council grows
+    client.vote_on_proposal(&ban_c, &a, &true, &None);
+    client.vote_on_proposal(&ban_c, &b, &true, &None);
+    client.vote_on_proposal(&ban_c, &c, &false, &None);
+    env.ledger().with_mut(|li| li.timestamp = client.get_proposal(&ban_c).voting_period_end + 1);
+    client.finalize_proposal(&ban_c);
+    assert!(client.execute_proposal(&ban_c, &a));
+    assert_eq!(client.get_user(&c).status, UserStatus::Banned);
+    assert!(client.get_council_members().contains(&c));
+
+    // No single member can lift the ban, whether by approving or suspending the user
+    assert_eq!(client.try_approve_user(&c, &a).err(), Some(Ok(Error::Unauthorized)));
+    assert_eq!(client.try_suspend_user(&c, &b, &String::from_str(&env, "Probation")).err(), Some(Ok(Error::Unauthorized)));
+    assert_eq!(client.get_user(&c).status, UserStatus::Banned);
+    let title = String::from_str(&env, "Proposal");
+    let open = client.create_proposal(&a, &ProposalType::UserBan, &title, &title, &sample_payload(&env, ProposalType::UserBan), &None);
+    assert_eq!(client.try_vote_on_proposal(&open, &c, &true, &None).err(), Some(Ok(Error::Unauthorized)));
+
+    let removal = passed_proposal(&env, &client, &a, ProposalType::MembershipChange, ProposalAction::RemoveCouncilMember(c.clone()));
+    let seats = client.get_council_members().len();
+    client.execute_proposal(&removal, &a);
+    let removed = env.events().all().iter().any(|(_, topics, _)| {
+        topics == (symbol_short!("DAORem"), c.clone()).into_val(&env)
+    });
+    assert!(removed);
+    assert!(!client.get_user(&c).is_dao_member);
+    assert!(!client.get_council_members().contains(&c));
+    assert_eq!(client.get_council_members().len(), seats - 1);
+    let open = client.create_proposal(&a, &ProposalType::UserBan, &title, &title, &sample_payload(&env, ProposalType::UserBan), &None);
+    assert_eq!(client.try_vote_on_proposal(&open, &c, &true, &None).err(), Some(Ok(Error::Unauthorized)));
+
+    // Banning a member takes their seat too
+    let ban = passed_proposal(&env, &client, &a, ProposalType::UserBan, ProposalAction::BanUser(b.clone()));
+    client.execute_proposal(&ban, &a);
+    assert_eq!(client.get_user(&b).status, UserStatus::Banned);
+    assert!(!client.get_user(&b).is_dao_member);
+    assert!(!client.get_council_members().contains(&b));
+
+    let stale = passed_proposal(&env, &client, &a, ProposalType::MembershipChange, ProposalAction::RemoveCouncilMember(c.clone()));
+    assert!(!client.execute_proposal(&stale, &a));
+    assert_eq!(client.get_proposal(&stale).status_reason, Some(String::from_str(&env, "not a council member")));
+
+    // A suspended member keeps the seat but has no say until approved again
+    let d = Address::generate(&env);
+    make_council_member(&env, &client, &d);
+    client.suspend_user(&d, &a, &String::from_str(&env, "Under investigation"));
+    let open = client.create_proposal(&a, &ProposalType::UserBan, &title, &title, &sample_payload(&env, ProposalType::UserBan), &None);
+    assert_eq!(client.try_vote_on_proposal(&open, &d, &true, &None).err(), Some(Ok(Error::Unauthorized)));
+    client.approve_user(&d, &a);
+    client.vote_on_proposal(&open, &d, &true, &None);
+}
+
+#[test]
+fn test_secret_ballots_commit_then_reveal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+
+    let mut config = client.get_proposal_config(&ProposalType::PolicyCreation);
+    assert_eq!(config.reveal_duration, 0);
+    config.reveal_duration = 86_400;
+    env.as_contract(&client.address, || {
+        dao_governance::execute_proposal_config_update(&env, config).unwrap();
+    });
+
+    let members = [Address::generate(&env), Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    for member in members.iter() {
+        make_council_member(&env, &client, member);
+    }
+    let title = String::from_str(&env, "Proposal");
+    let proposal_id = client.create_proposal(&council, &ProposalType::PolicyCreation, &title, &title, &sample_payload(&env, ProposalType::PolicyCreation), &None);
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.reveal_end, Some(proposal.voting_period_end + 86_400));
+    assert_eq!(
+        client.try_vote_on_proposal(&proposal_id, &council, &true, &None).err(),
+        Some(Ok(Error::InvalidVoteType))
+    );
+
+    let salt = BytesN::from_array(&env, &[7; 32]);
+    let ballots = [true, true, false];
+    for (member, vote) in members.iter().zip(ballots) {
+        client.commit_vote(&proposal_id, member, &secret_ballot::ballot_commitment(&env, proposal_id, member, vote, &salt));
+    }
+    let commitment = secret_ballot::ballot_commitment(&env, proposal_id, &members[0], true, &salt);
+    // members[3] copies another member's commitment without knowing its contents
+    client.commit_vote(&proposal_id, &members[3], &commitment);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("VoteCmt"), proposal_id).into_val(&env));
+    assert_eq!(client.try_commit_vote(&proposal_id, &members[0], &commitment).err(), Some(Ok(Error::AlreadyVoted)));
+    assert_eq!(
+        client.try_reveal_vote(&proposal_id, &members[0], &true, &salt, &None).err(),
+        Some(Ok(Error::VotingStillOpen))
+    );
+    // Enough weight is committed, but nothing is counted before the reveals close
+    assert_eq!(client.try_finalize_proposal(&proposal_id).err(), Some(Ok(Error::VotingStillOpen)));
+
+    env.ledger().with_mut(|li| li.timestamp = proposal.voting_period_end + 1);
+    assert_eq!(client.try_commit_vote(&proposal_id, &council, &commitment).err(), Some(Ok(Error::VotingPeriodEnded)));
+    client.reveal_vote(&proposal_id, &members[0], &true, &salt, &None);
+    let wrong_salt = BytesN::from_array(&env, &[8; 32]);
+    assert_eq!(
+        client.try_reveal_vote(&proposal_id, &members[1], &true, &wrong_salt, &None).err(),
+        Some(Ok(Error::RevealMismatch))
+    );
+    assert_eq!(
+        client.try_reveal_vote(&proposal_id, &members[2], &true, &salt, &None).err(),
+        Some(Ok(Error::RevealMismatch))
+    );
+    // A copied commitment never reveals, even with the original vote and salt
+    assert_eq!(
+        client.try_reveal_vote(&proposal_id, &members[3], &true, &salt, &None).err(),
+        Some(Ok(Error::RevealMismatch))
+    );
+    client.reveal_vote(&proposal_id, &members[1], &true, &salt, &None);
+    client.reveal_vote(&proposal_id, &members[2], &false, &salt, &None);
+    assert_eq!(
+        client.try_reveal_vote(&proposal_id, &members[0], &true, &salt, &None).err(),
+        Some(Ok(Error::AlreadyVoted))
+    );
+    assert_eq!(client.try_get_vote(&proposal_id, &members[0]).err(), Some(Ok(Error::VotingStillOpen)));
+    assert_eq!(client.get_proposal(&proposal_id).votes_for, 0);
+    assert_eq!(client.get_member_votes(&members[0], &0, &10).len(), 0);
+
+    // members[3] never reveals, so their vote counts as an abstention
+    env.ledger().with_mut(|li| li.timestamp = proposal.reveal_end.unwrap() + 1);
+    assert_eq!(
+        client.try_reveal_vote(&proposal_id, &members[3], &true, &salt, &None).err(),
+        Some(Ok(Error::VotingPeriodEnded))
+    );
+    assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Passed);
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!((proposal.votes_for, proposal.votes_against), (2, 1));
+    assert_eq!((proposal.yes_votes, proposal.no_votes), (2, 1));
+    assert!(client.get_vote(&proposal_id, &members[0]).vote);
+    assert_eq!(client.get_proposal_votes(&proposal_id, &0, &10).len(), 3);
+}
+
+#[test]
+fn test_batched_proposals_execute_all_or_nothing() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let villager = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    client.register_user(&villager, &None);
+
+    // A governance batch may mix action types
+    let draft = PolicyDraft {
+        title: String::from_str(&env, "Flood Cover"),
+        description: String::from_str(&env, "Cover for river flooding"),
+        params: policy_params(&env),
+    };
+    let mut config = PlatformConfig { council_size: 50, ..platform_config() };
+    config.penalty_rate = 900;
+    let batch = ProposalAction::Batch(vec![
+        &env,
+        ProposalAction::CreatePolicy(draft),
+        ProposalAction::Treasury(FinancialAction::FreezeFunds(String::from_str(&env, "Audit"))),
+        ProposalAction::UpdateConfig(config.clone()),
+    ]);
+    let batch_id = passed_proposal(&env, &client, &council, ProposalType::GovernanceUpdate, batch);
+    assert!(client.execute_proposal(&batch_id, &council));
+    assert_eq!(client.get_policy(&1).status, PolicyStatus::Active);
+    assert!(client.is_fund_frozen());
+    env.as_contract(&client.address, || {
+        assert_eq!(financial_management::FinancialManagementService::get_platform_config(&env).penalty_rate, 900);
+    });
+
+    // The withdrawal fails while funds are frozen, so the config change before it is undone
+    config.penalty_rate = 100;
+    let withdraw = FinancialAction::WithdrawReserve(ReserveWithdrawal {
+        recipient: council.clone(),
+        amount: 1,
+        purpose: String::from_str(&env, "Repairs"),
+    });
+    let failing = ProposalAction::Batch(vec![
+        &env,
+        ProposalAction::UpdateConfig(config),
+        ProposalAction::Treasury(withdraw),
+    ]);
+    let failing_id = passed_proposal(&env, &client, &council, ProposalType::GovernanceUpdate, failing);
+    assert_eq!(client.try_execute_proposal(&failing_id, &council).err(), Some(Ok(Error::FundFrozen)));
+    assert_eq!(client.get_proposal(&failing_id).status, ProposalStatus::Queued);
+    env.as_contract(&client.address, || {
+        assert_eq!(financial_management::FinancialManagementService::get_platform_config(&env).penalty_rate, 900);
+    });
+
+    // A stale first action invalidates the batch; a later one reverts what ran before it
+    let stale = ProposalAction::Batch(vec![&env, ProposalAction::ArchivePolicy(99), ProposalAction::ArchivePolicy(1)]);
+    let stale_id = passed_proposal(&env, &client, &council, ProposalType::PolicyArchival, stale);
+    assert!(!client.execute_proposal(&stale_id, &council));
+    assert_eq!(client.get_proposal(&stale_id).status, ProposalStatus::Invalid);
+    let late = ProposalAction::Batch(vec![&env, ProposalAction::ArchivePolicy(1), ProposalAction::ArchivePolicy(99)]);
+    let late_id = passed_proposal(&env, &client, &council, ProposalType::PolicyArchival, late);
+    assert_eq!(client.try_execute_proposal(&late_id, &council).err(), Some(Ok(Error::InvalidProposalPayload)));
+    assert_eq!(client.get_proposal(&late_id).status, ProposalStatus::Passed);
+    assert_eq!(client.get_policy(&1).status, PolicyStatus::Active);
+
+    // Each action is checked against the state the earlier ones left behind
+    let retire = ProposalAction::Batch(vec![&env, ProposalAction::ArchivePolicy(1), ProposalAction::DeletePolicy(1)]);
+    let retire_id = passed_proposal(&env, &client, &council, ProposalType::GovernanceUpdate, retire);
+    assert!(client.execute_proposal(&retire_id, &council));
+    assert!(client.try_get_policy(&1).is_err());
+
+    // Other types only batch their own actions, batches do not nest and size is capped
+    let mixed = ProposalAction::Batch(vec![&env, ProposalAction::ArchivePolicy(1), ProposalAction::BanUser(villager.clone())]);
+    let nested = ProposalAction::Batch(vec![&env, ProposalAction::Batch(vec![&env, ProposalAction::ArchivePolicy(1)])]);
+    let mut oversized = Vec::new(&env);
+    for _ in 0..11 {
+        oversized.push_back(ProposalAction::ArchivePolicy(1));
+    }
+    let title = String::from_str(&env, "Batch");
+    for payload in [mixed, nested, ProposalAction::Batch(Vec::new(&env)), ProposalAction::Batch(oversized)] {
+        assert_eq!(
+            client
+                .try_create_proposal(&council, &ProposalType::PolicyArchival, &title, &title, &payload.to_xdr(&env), &None)
+                .err(),
+            Some(Ok(Error::InvalidProposalPayload))
+        );
+    }
+    // Governance batches may not carry member or claim actions past their own type's rules
+    let slash = StakeSlash { member: villager.clone(), amount: 1 };
+    for action in [
+        ProposalAction::BanUser(villager.clone()),
+        ProposalAction::SlashStake(slash),
+        ProposalAction::RemoveCouncilMember(council.clone()),
+    ] {
+        let payload = ProposalAction::Batch(vec![&env, ProposalAction::UpdateConfig(platform_config()), action]);
+        assert_eq!(
+            client
+                .try_create_proposal(&council, &ProposalType::GovernanceUpdate, &title, &title, &payload.to_xdr(&env), &None)
+                .err(),
+            Some(Ok(Error::InvalidProposalPayload))
+        );
+    }
+    assert_eq!(client.get_user(&villager).status, UserStatus::Active);
+}
+
+#[test]
+fn test_quadratic_and_conviction_tallies() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    let token = setup_token(&env, &client, &council);
+    StellarAssetClient::new(&env, &token).mint(&council, &4_000_000_000);
+    client.stake(&council, &4_000_000_000);
+    assert_eq!(client.get_vote_weight(&council).total(), 5);
+
+    let mut quadratic = client.get_proposal_config(&ProposalType::UserApproval);
+    assert_eq!(quadratic.tally_mode, TallyMode::Linear);
+    quadratic.tally_mode = TallyMode::Quadratic;
+    let mut conviction = client.get_proposal_config(&ProposalType::PolicyCreation);
+    conviction.tally_mode = TallyMode::Conviction;
+    conviction.reveal_duration = 86_400;
+    env.as_contract(&client.address, || {
+        dao_governance::execute_proposal_config_update(&env, quadratic).unwrap();
+        // Secret ballots cannot tell how long a vote was held
+        assert!(matches!(
+            dao_governance::execute_proposal_config_update(&env, conviction.clone()),
+            Err(dao_governance::DAOGovernanceError::InvalidProposalConfig)
+        ));
+        conviction.reveal_duration = 0;
+        dao_governance::execute_proposal_config_update(&env, conviction).unwrap();
+    });
+
+    // A weight of five counts as its square root
+    let title = String::from_str(&env, "Proposal");
+    let approval = client.create_proposal(&council, &ProposalType::UserApproval, &title, &title, &sample_payload(&env, ProposalType::UserApproval), &None);
+    assert_eq!(client.get_proposal(&approval).tally_mode, TallyMode::Quadratic);
+    client.vote_on_proposal(&approval, &council, &true, &None);
+    assert_eq!(client.get_proposal(&approval).votes_for, 2);
+
+    // Three early yes votes outweigh four last-minute no votes
+    let policy_vote = client.create_proposal(&council, &ProposalType::PolicyCreation, &title, &title, &sample_payload(&env, ProposalType::PolicyCreation), &None);
+    let members = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for member in members.iter() {
+        make_council_member(&env, &client, member);
+    }
+    for member in members[..3].iter() {
+        client.vote_on_proposal(&policy_vote, member, &true, &None);
+    }
+    env.ledger().with_mut(|li| li.timestamp += 6 * 86_400);
+    for member in members[3..].iter() {
+        client.vote_on_proposal(&policy_vote, member, &false, &None);
+    }
+    // Conviction tallies keep growing, so they never settle before the close
+    assert_eq!(client.try_finalize_proposal(&policy_vote).err(), Some(Ok(Error::VotingStillOpen)));
+    env.ledger().with_mut(|li| li.timestamp += 86_400 + 1);
+    assert_eq!(client.finalize_proposal(&policy_vote), ProposalStatus::Passed);
+    let proposal = client.get_proposal(&policy_vote);
+    // 3 votes held 7 days (1.7x) against 4 held 1 day (1.1x)
+    assert_eq!((proposal.votes_for, proposal.votes_against), (5, 4));
+
+    // Conviction never makes up for missing turnout: two votes scale to 3.4 but quorum is 3 votes
+    let thin = client.create_proposal(&council, &ProposalType::PolicyCreation, &title, &title, &sample_payload(&env, ProposalType::PolicyCreation), &None);
+    assert_eq!(client.get_proposal(&thin).required_quorum, 3);
+    for member in members[..2].iter() {
+        client.vote_on_proposal(&thin, member, &true, &None);
+    }
+    env.ledger().with_mut(|li| li.timestamp = client.get_proposal(&thin).voting_period_end + 1);
+    assert_eq!(client.finalize_proposal(&thin), ProposalStatus::Expired);
+}
+
+#[test]
+fn test_claims_are_decided_by_tiered_dao_votes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    // A full council at the default `council_size` of 5
+    let members = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let client = setup_with_council(&env, &Vec::from_array(&env, members.clone()));
+    let council = members[0].clone();
+    let villager = Address::generate(&env);
+    client.register_user(&villager, &None);
+    let token = setup_token(&env, &client, &council);
+    StellarAssetClient::new(&env, &token).mint(&villager, &1_000_000_000);
+    let policy_id = create_policy(&env, &client, &council);
+    let subscription_id = client.create_subscription(&villager, &policy_id, &0);
+    client.process_payment(&villager, &subscription_id);
+
+    let file_claim = |amount: i128| {
+        let claim_id = client.create_claim(
+            &villager,
+            &subscription_id,
+            &amount,
+            &BytesN::from_array(&env, &[3; 32]),
+            &String::from_str(&env, "Hailstorm"),
+        );
+        (claim_id, client.get_claim(&claim_id).proposal_id.unwrap())
+    };
+    // 100 XLM, 1000 XLM and 5000 XLM
+    let (small, small_vote) = file_claim(1_000_000_000);
+    let (medium, medium_vote) = file_claim(10_000_000_000);
+    let (large, large_vote) = file_claim(50_000_000_000);
+
+    let proposal = client.get_proposal(&small_vote);
+    assert_eq!(proposal.proposal_type, ProposalType::ClaimResolution);
+    assert_eq!(proposal.claim_id, Some(small));
+    assert_eq!(proposal.voting_period_end - proposal.start_time, 3 * 86_400);
+    assert_eq!((proposal.required_quorum, proposal.approval_threshold), (3, 6000));
+    assert_eq!(client.get_proposal(&medium_vote).required_quorum, 5);
+    // Twice the base quorum is more than five members can cast, so it is capped at what they can
+    assert_eq!(client.get_proposal(&large_vote).required_quorum, 5);
+
+    // The claimer neither votes on nor withdraws their own claim
+    assert_eq!(client.try_vote_on_proposal(&small_vote, &villager, &true, &None).err(), Some(Ok(Error::Unauthorized)));
+    assert_eq!(client.try_cancel_proposal(&small_vote, &villager).err(), Some(Ok(Error::Unauthorized)));
+
+    let vote = |proposal_id: u64, yes: usize, no: usize| {
+        for (i, member) in members[..yes + no].iter().enumerate() {
+            client.vote_on_proposal(&proposal_id, member, &(i < yes), &None);
+        }
+    };
+    vote(small_vote, 2, 0);
+    // 3-2 is exactly 60%, which does not clear the bar
+    vote(medium_vote, 3, 2);
+    vote(large_vote, 4, 1);
+    env.ledger().with_mut(|li| li.timestamp += 3 * 86_400 + 1);
+    // Once voting has closed the claimer cannot restart a losing vote by editing the claim
+    assert_eq!(client.try_update_claim(&villager, &medium, &None, &None).err(), Some(Ok(Error::ClaimAlreadyProcessed)));
+
+    assert_eq!(client.finalize_proposal(&large_vote), ProposalStatus::Executed);
+    let (_, topics, _) = env.events().all().first().unwrap();
+    assert_eq!(topics, (symbol_short!("ClmApp"), large).into_val(&env));
+    assert_eq!(client.get_claim(&large).status, ClaimStatus::Approved);
+    let votes = client.get_claim_votes(&large);
+    assert_eq!(votes.len(), 5);
+    assert!(votes.get(0).unwrap().approve && !votes.get(4).unwrap().approve);
+
+    assert_eq!(client.finalize_proposal(&medium_vote), ProposalStatus::Rejected);
+    let claim = client.get_claim(&medium);
+    assert_eq!(claim.status, ClaimStatus::Rejected);
+    assert_eq!(claim.assessor_notes, String::from_str(&env, "rejected by DAO vote"));
+
+    assert_eq!(client.finalize_proposal(&small_vote), ProposalStatus::Expired);
+    let claim = client.get_claim(&small);
+    assert_eq!(claim.status, ClaimStatus::Rejected);
+    assert_eq!(claim.assessor_notes, String::from_str(&env, "vote closed without quorum"));
+
+    // A separate resolution proposal cannot go around a claim's own vote
+    let (pending, _) = file_claim(1_000_000_000);
+    let decision = ClaimDecision { claim_id: pending, approved: true, notes: String::from_str(&env, "Verified") };
+    let bypass = passed_proposal(&env, &client, &council, ProposalType::ClaimResolution, ProposalAction::ResolveClaim(decision));
+    assert!(!client.execute_proposal(&bypass, &council));
+    assert_eq!(client.get_claim(&pending).status, ClaimStatus::Submitted);
+}
+
+#[test]
+fn test_assessors_review_claims_without_conflicts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let council = Address::generate(&env);
+    let villager = Address::generate(&env);
+    let sibling = Address::generate(&env);
+    let assessor = Address::generate(&env);
+    let neighbour = Address::generate(&env);
+    make_council_member(&env, &client, &council);
+    for user in [&villager, &sibling, &assessor, &neighbour] {
+        client.register_user(user, &None);
+    }
+    client.set_household(&villager, &Some(7), &council);
+    client.set_household(&sibling, &Some(7), &council);
+    assert_eq!(client.try_set_household(&villager, &None, &assessor).err(), Some(Ok(Error::Unauthorized)));
+
+    let policy_id = create_policy(&env, &client, &council);
+    let subscription_id = client.create_subscription(&villager, &policy_id, &0);
+    let claim_id = client.create_claim(
+        &villager,
+        &subscription_id,
+        &150_000_000,
+        &BytesN::from_array(&env, &[5; 32]),
+        &String::from_str(&env, "Goat lost in flood"),
+    );
+
+    let assign = |assessors: Vec<Address>| client.try_assign_assessors(&council, &claim_id, &assessors).err();
+    assert_eq!(
+        client.try_assign_assessors(&assessor, &claim_id, &vec![&env, neighbour.clone()]).err(),
+        Some(Ok(Error::Unauthorized))
+    );
+    // Neither the claimer nor their household may assess
+    assert_eq!(assign(vec![&env, villager.clone()]), Some(Ok(Error::ConflictOfInterest)));
+    assert_eq!(assign(vec![&env, assessor.clone(), sibling.clone()]), Some(Ok(Error::ConflictOfInterest)));
+    assert_eq!(client.get_claim(&claim_id).status, ClaimStatus::Submitted);
+
+    client.assign_assessors(&council, &claim_id, &vec![&env, assessor.clone(), neighbour.clone()]);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("ClmAsgn"), claim_id).into_val(&env));
+    assert_eq!(client.get_claim(&claim_id).status, ClaimStatus::UnderReview);
+    assert_eq!(client.get_claim_assessors(&claim_id), vec![&env, assessor.clone(), neighbour.clone()]);
+    let mut crowd = Vec::new(&env);
+    for _ in 0..4 {
+        let extra = Address::generate(&env);
+        client.register_user(&extra, &None);
+        crowd.push_back(extra);
+    }
+    assert_eq!(assign(crowd), Some(Ok(Error::InvalidClaimData)));
+
+    let reasoning = String::from_str(&env, "Visited the field, carcass found");
+    assert_eq!(
+        client.try_submit_assessment(&sibling, &claim_id, &true, &reasoning).err(),
+        Some(Ok(Error::Unauthorized))
+    );
+    client.submit_assessment(&assessor, &claim_id, &true, &reasoning);
+    assert_eq!(
+        client.try_submit_assessment(&assessor, &claim_id, &true, &reasoning).err(),
+        Some(Ok(Error::AlreadyAssessed))
+    );
+    // Enough members agree to settle the vote early, but not while an assessor has yet to report
+    let proposal_id = client.get_claim(&claim_id).proposal_id.unwrap();
+    vote_as_new_members(&env, &client, proposal_id, 3, 0);
+    assert_eq!(client.try_finalize_proposal(&proposal_id).err(), Some(Ok(Error::VotingStillOpen)));
+    // Joining the claimer's household after assignment still rules the assessor out
+    client.set_household(&neighbour, &Some(7), &council);
+    let doubt = String::from_str(&env, "No sign of flooding");
+    assert_eq!(
+        client.try_submit_assessment(&neighbour, &claim_id, &false, &doubt).err(),
+        Some(Ok(Error::ConflictOfInterest))
+    );
+    client.set_household(&neighbour, &Some(8), &council);
+    env.ledger().with_mut(|li| li.timestamp += 2 * 86_400);
+    client.submit_assessment(&neighbour, &claim_id, &false, &doubt);
+    // With every finding in, members get a full voting period to weigh them
+    assert_eq!(client.get_proposal(&proposal_id).voting_period_end, env.ledger().timestamp() + 3 * 86_400);
+
+    let assessments = client.get_claim_assessments(&claim_id);
+    assert_eq!(assessments.len(), 2);
+    assert_eq!(assessments.get(0).unwrap().reasoning, reasoning);
+    let summary = client.get_assessment_summary(&claim_id);
+    assert_eq!(
+        summary,
+        AssessmentSummary { assessors: 2, approvals: 1, rejections: 1, approve_weight: 1, reject_weight: 1 }
+    );
+    // The claim's vote carries the assessors' findings and still decides it
+    assert_eq!(client.get_proposal(&proposal_id).assessment, summary);
 
-    let words = client.hello(&String::from_str(&env, "Dev"));
+    // An assessor who never reports holds the vote only until its window closes, and nobody
+    // can be assigned or report after that
+    let straggler = Address::generate(&env);
+    client.register_user(&straggler, &None);
+    client.assign_assessors(&council, &claim_id, &vec![&env, straggler.clone()]);
+    env.ledger().with_mut(|li| li.timestamp = client.get_proposal(&proposal_id).voting_period_end + 1);
+    assert_eq!(assign(vec![&env, sibling.clone()]), Some(Ok(Error::ClaimAlreadyProcessed)));
     assert_eq!(
-        words,
-        vec![
-            &env,
-            String::from_str(&env, "Hello"),
-            String::from_str(&env, "Dev"),
-        ]
+        client.try_submit_assessment(&straggler, &claim_id, &true, &reasoning).err(),
+        Some(Ok(Error::ClaimAlreadyProcessed))
     );
+    assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Executed);
+    assert_eq!(client.get_claim(&claim_id).status, ClaimStatus::Approved);
 }